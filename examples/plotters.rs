@@ -4,7 +4,6 @@ use plotters::prelude::*;
 extern crate stroke;
 use stroke::cubic_bezier::CubicBezier;
 use stroke::point_generic::PointN;
-use stroke::point::Point;
 
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -21,11 +20,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     (0f64,  1.77f64)];
 
 
-    let bezier = CubicBezier::new( 
-                PointN::new([0f64,  1.77f64]),
-                PointN::new([1.1f64, -1f64]),
-                PointN::new([4.3f64,3f64]),
-                PointN::new([3.2f64, -4f64]),
+    let bezier: CubicBezier<PointN<f64, 2>> = CubicBezier::new(
+                cp[0].into(),
+                cp[1].into(),
+                cp[2].into(),
+                cp[3].into(),
             );
 
     let bounds = bezier.bounding_box::<f64>();
@@ -35,12 +34,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let ymax = bounds[1].1;
 
     let nsteps: usize =  1000;                            
-    let mut bezier_graph: Vec<(f64, f64)> = Vec::with_capacity(nsteps);       
-    for t in 0..nsteps {
-        let t = t as f64 * 1f64/(nsteps as f64);
-        let p = bezier.eval_casteljau(t);
-        bezier_graph.push((p.axis(0), p.axis(1)));
-    }
+    let bezier_graph: Vec<(f64, f64)> = bezier.iter_uniform::<f64>(nsteps)
+                                            .map(|p| p.into())
+                                            .collect();
 
     let root = BitMapBackend::new("cubic_bezier_bounding_box.png", (640, 480)).into_drawing_area();
     root.fill(&WHITE)?;
@@ -0,0 +1,149 @@
+use super::*;
+use super::point::Point;
+use super::point_generic::PointN;
+
+
+/// A 2D affine transformation, stored as the upper two rows of a 3x3 matrix:
+/// ```text
+/// | a  b  c |   | x |
+/// | d  e  f | * | y |
+/// | 0  0  1 |   | 1 |
+/// ```
+/// Bezier curves are invariant under affine transformations, so a curve
+/// can be transformed by transforming its control points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Affine2 {
+    pub matrix: [[NativeFloat; 3]; 2],
+}
+
+impl Affine2 {
+
+    pub fn new(matrix: [[NativeFloat; 3]; 2]) -> Self {
+        Affine2 { matrix }
+    }
+
+    pub fn identity() -> Self {
+        Affine2::new([[1.0, 0.0, 0.0],
+                      [0.0, 1.0, 0.0]])
+    }
+
+    pub fn translation(x: NativeFloat, y: NativeFloat) -> Self {
+        Affine2::new([[1.0, 0.0, x],
+                      [0.0, 1.0, y]])
+    }
+
+    pub fn scale(x: NativeFloat, y: NativeFloat) -> Self {
+        Affine2::new([[x, 0.0, 0.0],
+                      [0.0, y, 0.0]])
+    }
+
+    /// Counter-clockwise rotation around the origin by angle (in radians)
+    pub fn rotation(angle: NativeFloat) -> Self {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Affine2::new([[cos, -sin, 0.0],
+                      [sin, cos, 0.0]])
+    }
+
+    /// Returns the transformation which applies self first and then other
+    pub fn then(&self, other: &Affine2) -> Self {
+        let (m, o) = (&self.matrix, &other.matrix);
+        let mut res = [[0.0; 3]; 2];
+        for row in 0..2 {
+            for col in 0..3 {
+                res[row][col] = o[row][0] * m[0][col] + o[row][1] * m[1][col];
+            }
+            res[row][2] += o[row][2];
+        }
+        Affine2::new(res)
+    }
+
+    /// Applies the transformation to the first two axes of any point,
+    /// all remaining axes are left unchanged.
+    pub fn apply<P>(&self, p: P) -> P
+    where
+    P: Point<Scalar = NativeFloat> + Copy,
+    {
+        debug_assert!(P::DIM >= 2);
+        let m = &self.matrix;
+        let (x, y) = (p.axis(0), p.axis(1));
+        let mut res = p;
+        res.set_axis(0, m[0][0] * x + m[0][1] * y + m[0][2]);
+        res.set_axis(1, m[1][0] * x + m[1][1] * y + m[1][2]);
+        return res
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Affine2::identity()
+    }
+}
+
+impl PointN<NativeFloat, 2> {
+    /// Returns the point transformed by m
+    pub fn transform(&self, m: &Affine2) -> Self {
+        m.apply(*self)
+    }
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+    use super::cubic_bezier::CubicBezier;
+    use super::quadratic_bezier::QuadraticBezier;
+    use super::line::LineSegment;
+
+    fn test_transform() -> Affine2 {
+        Affine2::scale(2.0, -0.5)
+            .then(&Affine2::rotation(0.7))
+            .then(&Affine2::translation(-3.0, 1.5))
+    }
+
+    #[test]
+    fn transform_point() {
+        let p = PointN::new([1f64, 2f64]);
+        assert!(p.transform(&Affine2::identity()) == p);
+        assert!(p.transform(&Affine2::translation(-1.0, 3.0)) == PointN::new([0f64, 5f64]));
+        assert!(p.transform(&Affine2::scale(2.0, 0.5)) == PointN::new([2f64, 1f64]));
+        let rotated = p.transform(&Affine2::rotation(core::f64::consts::FRAC_PI_2));
+        assert!(rotated.distance(&PointN::new([-2f64, 1f64])) < 1e-15);
+        // composition applies the transformations in order
+        let composed = p.transform(&Affine2::scale(2.0, 0.5).then(&Affine2::translation(-1.0, 3.0)));
+        assert!(composed == PointN::new([1f64, 4f64]));
+    }
+
+    /// Transforming the control points and then evaluating has to be equal
+    /// to evaluating and then transforming the result
+    #[test]
+    fn transform_affine_invariance() {
+        let m = test_transform();
+        let cubic = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let quadratic = QuadraticBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let line = LineSegment::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let (cubic_t, quadratic_t, line_t) = (cubic.transform(&m), quadratic.transform(&m), line.transform(&m));
+
+        let max_err = 1e-12;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(cubic_t.eval(t).distance(&cubic.eval(t).transform(&m)) < max_err);
+            assert!(quadratic_t.eval(t).distance(&quadratic.eval(t).transform(&m)) < max_err);
+            assert!(line_t.eval(t).distance(&line.eval(t).transform(&m)) < max_err);
+        }
+    }
+}
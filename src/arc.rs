@@ -0,0 +1,260 @@
+//! Approximation of circular and elliptical arcs with cubic bezier curves.
+//! Only 2D curves are supported, the first two axes of the points are used as x and y.
+//! Angles are given in radians, counter-clockwise from the positive x axis.
+//! The module requires the 'alloc' feature.
+
+use super::*;
+use super::point::Point;
+use super::cubic_bezier::CubicBezier;
+
+use alloc::vec::Vec;
+
+/// Maximum sweep angle of a single cubic segment of an arc, see arc_to_beziers()
+const ARC_MAX_SEGMENT_SWEEP: NativeFloat = core::f64::consts::FRAC_PI_2 as NativeFloat;
+
+
+/// Approximates the circular arc around center from start_angle to end_angle with cubic bezier curves.
+/// The arc runs counter-clockwise if end_angle > start_angle and clockwise otherwise,
+/// sweeps of more than a full turn are drawn as they are.
+/// The arc is split into the smallest number of equal spans of at most 90°, each of which is
+/// approximated by a single curve, see arc_segment(). The curves are connected, the first starts at
+/// start_angle and the last ends at end_angle. A zero sweep yields a single degenerate curve.
+pub fn arc_to_beziers<P, F>(center: P, radius: F, start_angle: F, end_angle: F) -> Vec<CubicBezier<P>>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    debug_assert!(P::DIM == 2);
+    let radius: NativeFloat = radius.into();
+    let to_point = |[x, y]: [NativeFloat; 2]| {
+        let mut p = center;
+        p.set_axis(0, center.axis(0) + radius * x);
+        p.set_axis(1, center.axis(1) + radius * y);
+        p
+    };
+    unit_arc_segments(start_angle.into(), end_angle.into())
+        .map(|[p0, p1, p2, p3]| CubicBezier::new(to_point(p0), to_point(p1), to_point(p2), to_point(p3)))
+        .collect()
+}
+
+
+/// Approximates the elliptical arc from start to end given in the endpoint parameterization of
+/// the SVG path command "A rx ry x_rotation large_arc sweep x y" with cubic bezier curves.
+/// The ellipse with radii rx and ry is rotated by x_rotation (in radians, unlike SVG which uses degrees).
+/// Of the four arcs through start and end, large_arc selects one that sweeps more than 180°
+/// and sweep one that runs in the direction of positive angles.
+/// The arc is converted to its center parameterization (SVG 1.1, appendix F.6.5) and split like arc_to_beziers().
+/// As in SVG, negative radii are replaced by their absolute values and radii that are too small to connect
+/// the end points are scaled up uniformly until the ellipse fits (F.6.6). If a radius is zero, the arc is
+/// a straight line which is returned as a single curve, if start and end coincide, the result is empty.
+pub fn endpoint_arc_to_beziers<P, F>(start: P, end: P, rx: F, ry: F, x_rotation: F, large_arc: bool, sweep: bool) -> Vec<CubicBezier<P>>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    debug_assert!(P::DIM == 2);
+    let mut arc = Vec::new();
+    if start == end {
+        return arc
+    }
+    let mut rx = rx.into().abs();
+    let mut ry = ry.into().abs();
+    if rx < EPSILON || ry < EPSILON {
+        let third = (end - start) * (1.0 / 3.0);
+        arc.push(CubicBezier::new(start, start + third, end - third, end));
+        return arc
+    }
+    let (sin_phi, cos_phi) = x_rotation.into().sin_cos();
+    let (x1, y1) = (start.axis(0), start.axis(1));
+    let (x2, y2) = (end.axis(0), end.axis(1));
+
+    // start point in the coordinate system of the ellipse, with the origin half way between the end points
+    let dx = (x1 - x2) * 0.5;
+    let dy = (y1 - y2) * 0.5;
+    let x1p = cos_phi * dx + sin_phi * dy;
+    let y1p = -sin_phi * dx + cos_phi * dy;
+
+    // correct out of range radii
+    let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    // center in the coordinate system of the ellipse
+    let numerator = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+    let denominator = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+    let mut coef = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coef = -coef;
+    }
+    let cxp = coef * rx * y1p / ry;
+    let cyp = -coef * ry * x1p / rx;
+    let cx = cos_phi * cxp - sin_phi * cyp + (x1 + x2) * 0.5;
+    let cy = sin_phi * cxp + cos_phi * cyp + (y1 + y2) * 0.5;
+
+    // angles of the end points on the unit circle before scaling and rotating
+    let start_angle = ((y1p - cyp) / ry).atan2((x1p - cxp) / rx);
+    let end_angle = ((-y1p - cyp) / ry).atan2((-x1p - cxp) / rx);
+    let tau = 2.0 * core::f64::consts::PI as NativeFloat;
+    let mut delta = (end_angle - start_angle) % tau;
+    if sweep && delta < 0.0 {
+        delta += tau;
+    } else if !sweep && delta > 0.0 {
+        delta -= tau;
+    }
+
+    let to_point = |[x, y]: [NativeFloat; 2]| {
+        let mut p = start;
+        p.set_axis(0, cx + cos_phi * rx * x - sin_phi * ry * y);
+        p.set_axis(1, cy + sin_phi * rx * x + cos_phi * ry * y);
+        p
+    };
+    arc.extend(unit_arc_segments(start_angle, start_angle + delta)
+        .map(|[p0, p1, p2, p3]| CubicBezier::new(to_point(p0), to_point(p1), to_point(p2), to_point(p3))));
+    // interpolate the given end points exactly
+    arc[0].start = start;
+    let last = arc.len() - 1;
+    arc[last].end = end;
+    return arc
+}
+
+
+/// Returns the control points of the curves approximating the arc of the unit circle around the origin
+/// from start_angle to end_angle, split into spans of at most ARC_MAX_SEGMENT_SWEEP.
+pub(crate) fn unit_arc_segments(start_angle: NativeFloat, end_angle: NativeFloat) -> impl Iterator<Item = [[NativeFloat; 2]; 4]> {
+    let sweep = end_angle - start_angle;
+    let n = ((sweep.abs() / ARC_MAX_SEGMENT_SWEEP).ceil() as usize).max(1);
+    // compute the angles of the joints from the same expression, so that consecutive curves
+    // share their end points exactly
+    let angle = move |i: usize| if i == n { end_angle } else { start_angle + sweep * (i as NativeFloat / n as NativeFloat) };
+    (0..n).map(move |i| arc_segment(angle(i), angle(i + 1)))
+}
+
+
+/// Returns the control points of a single curve approximating the arc of the unit circle
+/// from angle a0 to angle a1. The inner control points lie on the tangents at the end points
+/// at the distance kappa = 4/3 * tan(sweep / 4), which is the optimal choice for a curve that
+/// interpolates the circle at its end points and the middle of the arc.
+/// The radial error of a 90° span is at most 2.7e-4 and decreases with the sixth power of the sweep.
+fn arc_segment(a0: NativeFloat, a1: NativeFloat) -> [[NativeFloat; 2]; 4] {
+    let kappa = 4.0 / 3.0 * ((a1 - a0) / 4.0).tan();
+    let (sin0, cos0) = a0.sin_cos();
+    let (sin1, cos1) = a1.sin_cos();
+    [
+        [cos0, sin0],
+        [cos0 - kappa * sin0, sin0 + kappa * cos0],
+        [cos1 + kappa * sin1, sin1 - kappa * cos1],
+        [cos1, sin1],
+    ]
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+    use super::point_generic::PointN;
+    use core::f64::consts::PI;
+
+    #[test]
+    fn arc_approximation_error()
+    {
+        let center = PointN::new([1.5f64, -2f64]);
+        let radius = 3f64;
+        // define closure for the circle
+        let circle = |p: PointN<f64, 2>| -> f64 {
+            p.distance(&center) - radius
+        };
+        // radial drift of a 90° span relative to the radius
+        let max_drift_perc  = 0.0273;
+        let max_error       = max_drift_perc * 0.01 * radius;
+
+        for (start_angle, end_angle, segments) in [(0.0, 0.5 * PI, 1), (0.3, 2.0, 2), (1.0, -2.5, 3), (0.0, 2.0 * PI, 4), (-0.2, 0.1, 1)].iter() {
+            let arc = arc_to_beziers(center, radius, *start_angle, *end_angle);
+            assert!(arc.len() == *segments);
+
+            let start = PointN::new([center.axis(0) + radius * start_angle.cos(), center.axis(1) + radius * start_angle.sin()]);
+            let end = PointN::new([center.axis(0) + radius * end_angle.cos(), center.axis(1) + radius * end_angle.sin()]);
+            assert!(arc[0].start.distance(&start) < 1e-14);
+            assert!(arc[arc.len() - 1].end.distance(&end) < 1e-14);
+            for pair in arc.windows(2) {
+                assert!(pair[0].end == pair[1].start);
+            }
+
+            let nsteps =  1000;
+            for segment in arc.iter() {
+                for t in 0..=nsteps {
+                    let t = t as f64 * 1f64/(nsteps as f64);
+                    let point = segment.eval(t);
+                    let contour = circle(point);
+                    assert!( contour.abs() <= max_error );
+                }
+                // the curve interpolates the circle in the middle of the span
+                assert!( circle(segment.eval(0.5)).abs() < 1e-14 );
+            }
+        }
+        // the curves run in the direction of the sweep
+        let clockwise = arc_to_beziers(center, radius, 0.5 * PI, 0.0);
+        assert!(clockwise[0].eval(0.1).axis(0) > center.axis(0));
+        assert!(clockwise[0].start.axis(1) > clockwise[0].eval(0.1).axis(1));
+    }
+
+    #[test]
+    fn endpoint_arc() {
+        // quarter of the ellipse (x/2)^2 + y^2 = 1
+        let start = PointN::new([2f64, 0f64]);
+        let end = PointN::new([0f64, 1f64]);
+        let arc = endpoint_arc_to_beziers(start, end, 2.0, 1.0, 0.0, false, true);
+        assert!(arc.len() == 1);
+        assert!(arc[0].start == start && arc[0].end == end);
+        let max_error = 0.0273 * 0.01 * 2.0;
+        let nsteps = 20;
+        for i in 0..=nsteps {
+            let angle = i as f64 * 0.5 * PI / (nsteps as f64);
+            let reference = PointN::new([2.0 * angle.cos(), angle.sin()]);
+            assert!(arc[0].project::<f64>(reference).1 <= max_error);
+        }
+        // the other three quarters with the same end points
+        let large = endpoint_arc_to_beziers(start, end, 2.0, 1.0, 0.0, true, false);
+        assert!(large.len() == 3);
+        assert!(large[1].end.distance(&PointN::new([-2f64, 0f64])) < 1e-14);
+        for segment in large.iter() {
+            assert!(segment.start.axis(0) <= 2.0 && segment.eval(0.5).axis(1) < 1.0);
+        }
+
+        // the same ellipse rotated by 90°
+        let start = PointN::new([0f64, 2f64]);
+        let end = PointN::new([-1f64, 0f64]);
+        let arc = endpoint_arc_to_beziers(start, end, 2.0, 1.0, 0.5 * PI, false, true);
+        let ellipse = |p: PointN<f64, 2>| -> f64 {
+            (p.axis(0) * p.axis(0) + p.axis(1) * p.axis(1) * 0.25).sqrt() - 1.0
+        };
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(ellipse(arc[0].eval(t)).abs() <= max_error);
+        }
+
+        // the radii are too small and scaled to a half circle around (2,0)
+        let start = PointN::new([0f64, 0f64]);
+        let end = PointN::new([4f64, 0f64]);
+        let arc = endpoint_arc_to_beziers(start, end, 1.0, -1.0, 0.0, false, true);
+        assert!(arc.len() == 2);
+        assert!(arc[0].end.distance(&PointN::new([2f64, -2f64])) < 1e-14);
+
+        // degenerate arcs
+        assert!(endpoint_arc_to_beziers(start, start, 1.0, 1.0, 0.0, false, true).is_empty());
+        let line = endpoint_arc_to_beziers(start, end, 0.0, 1.0, 0.0, false, true);
+        assert!(line.len() == 1 && line[0].eval(0.5).distance(&PointN::new([2f64, 0f64])) < 1e-14);
+    }
+}
@@ -1,5 +1,17 @@
 use super::*;
 use super::point::Point;
+use super::line::LineSegment;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Maximum recursion depth for isolating the roots of the derivative in Bezier::bounding_box()
+const BBOX_MAX_DEPTH: usize = 32;
+/// Maximum recursion depth of Bezier::arclen_adaptive()
+const ARCLEN_MAX_DEPTH: usize = 16;
+/// Maximum recursion depth when flattening a Bezier curve, see Bezier::flattened()
+#[cfg(feature = "alloc")]
+const FLATTEN_MAX_DEPTH: usize = 16;
 
 /// General implementation of a Bezier curve of arbitrary degree.
 /// The curve is solely defined by an array of 'control_points'. The degree is defined as degree = control_points.len() - 1.
@@ -9,12 +21,16 @@ use super::point::Point;
 /// const generic parameters:
 /// N: Number of control points
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "P: Serialize",
+                                          deserialize = "P: Deserialize<'de>")))]
 pub struct Bezier<P, const N: usize> 
 where 
 P: Point + Copy,
 {
     /// Control points which define the curve and hence its degree
-    control_points: [P; N],
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_array"))]
+    pub(crate) control_points: [P; N],
 }
 
 impl<P: Point, const N: usize> IntoIterator for Bezier<P, N> {
@@ -44,6 +60,43 @@ P: Add + Sub + Copy
         }
     }
 
+    /// Returns the same curve traversed in the opposite direction,
+    /// i.e. `self.reverse().eval(t) == self.eval(1-t)`
+    pub fn reverse(&self) -> Self {
+        let mut control_points = self.control_points;
+        control_points.reverse();
+        Bezier{
+            control_points
+        }
+    }
+
+
+    /// Returns an iterator over nsteps+1 points of the curve evaluated at equally spaced
+    /// parameters from t=0 to t=1 (inclusive) with eval().
+    /// The first and last point are exactly the first and last control point.
+    pub fn iter_uniform<F>(&self, nsteps: usize) -> impl Iterator<Item = P>
+    where
+    F: Float,
+    P: Add<P, Output = P>
+        + Sub<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Mul<F, Output = F>
+        + Into<F>
+    {
+        let curve = *self;
+        (0..=nsteps).map(move |i| {
+            if i == 0 {
+                curve.control_points[0]
+            } else if i == nsteps {
+                curve.control_points[N - 1]
+            } else {
+                let t: F = (i as NativeFloat / nsteps as NativeFloat).into();
+                curve.eval(t)
+            }
+        })
+    }
+
 
     /// Evaluate a point on the curve at point 't' which should be in the interval [0,1]
     /// This is implemented using De Casteljau's algorithm (over a temporary array with const generic sizing)
@@ -69,6 +122,120 @@ P: Add + Sub + Copy
     }
 
 
+    /// Evaluate a point on the curve at 't' in [0,1] in O(n) instead of the O(n^2) of eval()
+    /// with the nested (horner) form of the bernstein polynomials:
+    ///     B(t) = (1-t)^n * sum_i=0_to_n( binomial(n, i) * u^i * P_i )  with u = t / (1-t)
+    /// The sum is a polynomial in u which is evaluated with horner's scheme, while the binomial coefficients
+    /// are updated incrementally. For t > 0.5 the roles of t and 1-t are swapped to keep u <= 1.
+    /// Unlike de casteljau's algorithm, which only forms convex combinations of the control points,
+    /// the intermediate sums are scaled by binomial coefficients (up to binomial(n, n/2)) and the result
+    /// by (1-t)^n, so rounding errors grow with the degree. Prefer eval() for high degrees or where
+    /// the points must stay exactly within the convex hull.
+    pub fn eval_horner<F>(&self, t: F) -> P
+    where
+    F: Float,
+    P: Add<P, Output = P>
+        + Sub<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Mul<F, Output = F>
+        + Into<F>
+    {
+        let n = N - 1;
+        let one: F = 1.0.into();
+        // evaluate from the end point closer to t, so that u <= 1
+        let reversed = t > 0.5.into();
+        let (t, s) = if reversed { (one - t, t) } else { (t, one - t) };
+        let control_point = |i: usize| if reversed { self.control_points[n - i] } else { self.control_points[i] };
+
+        let u = t / s;
+        let mut binomial = one;
+        let mut acc = control_point(n);
+        let mut s_n = one;
+        for i in (0..n).rev() {
+            // binomial(n, i) = binomial(n, i+1) * (i+1) / (n-i)
+            binomial = binomial * ((i + 1) as NativeFloat).into() / ((n - i) as NativeFloat).into();
+            acc = acc * u + control_point(i) * binomial;
+            s_n = s_n * s;
+        }
+        acc * s_n
+    }
+
+
+    /// Approximates the arc length of the curve by flattening it with straight line segments.
+    /// Remember arclen also works by linear approximation, not the integral, so we have to accept error!
+    /// This approximation is unfeasable if desired accuracy is greater than 2 decimal places
+    pub fn arclen<F>(&self, nsteps: usize) -> F
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let stepsize: NativeFloat = 1.0/(nsteps as NativeFloat);
+        let mut arclen: NativeFloat = 0.0;
+        for t in 0..nsteps {
+            let t = t as NativeFloat * 1.0/(nsteps as NativeFloat);
+            let p1 = self.eval(t.into());
+            let p2 = self.eval(t.into()+stepsize.into());
+
+            arclen = arclen + p1.distance(&p2);
+        }
+        return arclen.into()
+    }
+
+    /// Returns the length of the control polygon, which is an upper bound of the arc length
+    /// (together with chord_length() as a lower bound)
+    pub fn control_polygon_length<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let length: NativeFloat = self.control_points.windows(2)
+                                    .map(|w| w[0].distance(&w[1]))
+                                    .sum();
+        return length.into()
+    }
+
+    /// Returns the distance between the start and end point, which is a lower bound of the arc length
+    pub fn chord_length<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        return self.control_points[0].distance(&self.control_points[N-1]).into()
+    }
+
+    /// Computes the arc length of the curve without sampling it: the arc length lies between the chord length
+    /// and the length of the control polygon, which converge to each other when the curve is subdivided.
+    /// If they differ by less than 'tolerance', their average is returned (which is within tolerance/2 of the arc length),
+    /// otherwise the curve is split in half and both halves are measured recursively with half the tolerance each.
+    /// The subdivision depth is capped at ARCLEN_MAX_DEPTH.
+    pub fn arclen_adaptive<F>(&self, tolerance: F) -> F
+    where
+    F: Float
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>
+    {
+        return self.arclen_adaptive_recursive(tolerance.into(), 0).into()
+    }
+
+    fn arclen_adaptive_recursive(&self, tolerance: NativeFloat, depth: usize) -> NativeFloat {
+        let polygon_len = self.control_polygon_length::<NativeFloat>();
+        let chord_len = self.chord_length::<NativeFloat>();
+        if polygon_len - chord_len < tolerance || depth >= ARCLEN_MAX_DEPTH {
+            return (polygon_len + chord_len) * 0.5
+        }
+        let (left, right) = self.split::<NativeFloat>(0.5);
+        left.arclen_adaptive_recursive(tolerance * 0.5, depth + 1)
+            + right.arclen_adaptive_recursive(tolerance * 0.5, depth + 1)
+    }
+
+
     pub fn split<F>(&self, t: F) -> (Self, Self)
     where
     F: Float,
@@ -99,12 +266,283 @@ P: Add + Sub + Copy
         return ( Bezier{ control_points: left }, Bezier{ control_points: right })
     }
 
+    /// Return the bounding box of the curve as an array of (min, max) tuples for each dimension (its index)
+    /// The extrema of each dimension are found at the endpoints or at the roots of the derivative,
+    /// which are isolated by recursively subdividing the derivative's (1-dimensional) Bernstein polynomial:
+    /// a span can't contain a root if all its coefficients have the same sign (variation diminishing property).
+    /// This works for any degree, roots are resolved up to a parameter interval of 2^-BBOX_MAX_DEPTH.
+    pub fn bounding_box<F>(&self) -> [(F, F); P::DIM]
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let mut bounds = [(0.0.into(), 0.0.into()); P::DIM];
+        for dim in 0..P::DIM {
+            let mut min = self.control_points[0].axis(dim);
+            let mut max = self.control_points[N-1].axis(dim);
+            if min > max {
+                core::mem::swap(&mut min, &mut max);
+            }
+            // bernstein coefficients of the derivative for this dimension (up to the constant factor of the degree)
+            let mut coeffs = [0.0 as NativeFloat; N];
+            for i in 0..N-1 {
+                coeffs[i] = self.control_points[i+1].axis(dim) - self.control_points[i].axis(dim);
+            }
+            Self::bernstein_roots(coeffs, N-1, 0.0, 1.0, 0, &mut |t: NativeFloat| {
+                let value = self.eval::<F>(t.into()).axis(dim);
+                if value < min {
+                    min = value;
+                }
+                if value > max {
+                    max = value;
+                }
+            });
+            bounds[dim] = (min.into(), max.into());
+        }
+        return bounds
+    }
+
+    /// Return the bounding box of the control points as an array of (min, max) tuples for each dimension.
+    /// It always contains the curve (convex hull property) and needs no root finding,
+    /// but is generally larger than the tight bounding_box().
+    pub fn control_box<F>(&self) -> [(F, F); P::DIM]
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        return point::bounding_box(&self.control_points)
+    }
+
+    /// Recursively isolates the roots of the 1-dimensional bernstein polynomial given by
+    /// the first 'len' coefficients in 'coeffs' over the parameter interval [t0, t1]
+    /// and calls 'found' with the approximate parameter of each root
+    fn bernstein_roots<R>(coeffs: [NativeFloat; N], len: usize, t0: NativeFloat, t1: NativeFloat, depth: usize, found: &mut R)
+    where
+    R: FnMut(NativeFloat),
+    {
+        // all coefficients with the same (strict) sign: no root in this span
+        // all coefficients zero: the polynomial vanishes identically, so the endpoints suffice
+        if coeffs[..len].iter().all(|c| *c > 0.0)
+            || coeffs[..len].iter().all(|c| *c < 0.0)
+            || coeffs[..len].iter().all(|c| c.abs() < EPSILON) {
+            return
+        }
+        if depth >= BBOX_MAX_DEPTH || len < 2 {
+            found((t0 + t1) * 0.5);
+            return
+        }
+        // split the polynomial in half with de casteljau's algorithm
+        let mut left = coeffs;
+        let mut right = coeffs;
+        let mut casteljau = coeffs;
+        for i in 1..=len {
+            left[i-1] = casteljau[0];
+            right[len-i] = casteljau[len-i];
+            for j in 0..len-i {
+                casteljau[j] = (casteljau[j] + casteljau[j+1]) * 0.5;
+            }
+        }
+        let mid = (t0 + t1) * 0.5;
+        Self::bernstein_roots(left, len, t0, mid, depth + 1, found);
+        Self::bernstein_roots(right, len, mid, t1, depth + 1, found);
+    }
+
+    /// Returns the same curve with one more control point (degree elevation).
+    /// With the degree n = N-1, the new control points are the convex combinations
+    ///     Q_0 = P_0,  Q_i = i/(n+1) * P_i-1 + (1 - i/(n+1)) * P_i,  Q_n+1 = P_n
+    /// The size of the returned curve is an expression of N, so generic code
+    /// calling elevate() has to repeat the bound `[(); N+1]: Sized`.
+    pub fn elevate(&self) -> Bezier<P, {N+1}>
+    where
+    [(); N+1]: Sized,
+    {
+        let mut new_points: [P; N+1] = [P::default(); N+1];
+        new_points[0] = self.control_points[0];
+        new_points[N] = self.control_points[N-1];
+        for i in 1..N {
+            let alpha = i as NativeFloat / N as NativeFloat;
+            new_points[i] = self.control_points[i-1] * alpha + self.control_points[i] * (1.0 - alpha);
+        }
+        return Bezier::new(new_points)
+    }
+
+
+    /// Returns the curve with one control point less (degree reduction) which approximates self,
+    /// along with an error estimate, which is the maximum distance between the two curves
+    /// sampled at the same parameters.
+    /// The control points are found by inverting the degree elevation formula (see elevate())
+    /// from the first control point forwards for the first half and from the last control point
+    /// backwards for the second half. If the reduced curve has an odd number of control points,
+    /// the middle one is the average of both directions.
+    /// The result is exact (up to rounding) if self was obtained by elevating a curve.
+    /// Since this is generally an approximation, the returned error should be checked against
+    /// the desired tolerance. If it is exceeded, the curve should be split and each part reduced separately.
+    /// At least two control points are needed, so generic code calling reduce() has to repeat
+    /// the bounds `[(); N-1]: Sized, [(); N-2]: Sized`.
+    pub fn reduce<F>(&self) -> (Bezier<P, {N-1}>, F)
+    where
+    F: Float,
+    NativeFloat: Into<F>,
+    [(); N-1]: Sized,
+    [(); N-2]: Sized,
+    {
+        // degree of self and index of the last reduced control point
+        let n = (N - 1) as NativeFloat;
+        let m = N - 2;
+        let mut new_points: [P; N-1] = [P::default(); N-1];
+        // forward: R_i = (n * P_i - i * R_i-1) / (n - i)
+        new_points[0] = self.control_points[0];
+        for i in 1..=m/2 {
+            new_points[i] = (self.control_points[i] * n - new_points[i-1] * i as NativeFloat) * (1.0 / (n - i as NativeFloat));
+        }
+        let forward_middle = new_points[m/2];
+        // backward: R_i-1 = (n * P_i - (n - i) * R_i) / i
+        new_points[m] = self.control_points[N-1];
+        for i in (m.div_ceil(2) + 1..=m).rev() {
+            new_points[i-1] = (self.control_points[i] * n - new_points[i] * (n - i as NativeFloat)) * (1.0 / i as NativeFloat);
+        }
+        if m.is_multiple_of(2) {
+            new_points[m/2] = (forward_middle + new_points[m/2]) * 0.5;
+        }
+        let reduced = Bezier::new(new_points);
+
+        // sample the maximum deviation between the curves
+        let nsteps: usize = 100;
+        let mut max_err: NativeFloat = 0.0;
+        for t in 1..nsteps {
+            let t = t as NativeFloat / nsteps as NativeFloat;
+            let err = (self.eval(t) - reduced.eval(t)).squared_length();
+            if err > max_err {
+                max_err = err;
+            }
+        }
+        return (reduced, max_err.sqrt().into())
+    }
+
+
+    /// Returns true if all inner control points lie within tolerance of the baseline,
+    /// the segment from the first to the last control point, so the curve can be
+    /// approximated by that segment.
+    /// If the first and last control point (nearly) coincide, the distances to the first
+    /// control point are used instead, i.e. the curve is only linear if it's (nearly) a point.
+    pub fn is_linear<F>(&self, tolerance: F) -> bool
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>,
+    {
+        // a point or a segment has no inner control points
+        if N <= 2 {
+            return true
+        }
+        let baseline = LineSegment::new(self.control_points[0], self.control_points[N-1]);
+        self.control_points[1..N-1]
+            .iter()
+            .all(|p| baseline.distance_to_point::<F>(*p) <= tolerance)
+    }
+
+
+    /// Returns the points of a polyline approximating the curve (flattening),
+    /// such that the deviation between the polyline and the curve does not exceed 'tolerance'.
+    /// The curve is recursively split in half until each piece is linear within 'tolerance' (see is_linear()).
+    /// Both endpoints are included. Each level of recursion keeps two copies of the control points on the stack,
+    /// so the depth is capped at FLATTEN_MAX_DEPTH (at most 2^FLATTEN_MAX_DEPTH segments),
+    /// which also guarantees termination for unreasonably small tolerances.
+    #[cfg(feature = "alloc")]
+    pub fn flattened<F>(&self, tolerance: F) -> Vec<P>
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>,
+    {
+        let mut points = Vec::new();
+        points.push(self.control_points[0]);
+        self.flatten_recursive(tolerance, 0, &mut points);
+        points
+    }
+
+    /// Recursive part of flattened(), pushes the end points of the linear pieces
+    #[cfg(feature = "alloc")]
+    fn flatten_recursive<F>(&self, tolerance: F, depth: usize, points: &mut Vec<P>)
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>,
+    {
+        if depth >= FLATTEN_MAX_DEPTH || self.is_linear(tolerance) {
+            points.push(self.control_points[N-1]);
+            return;
+        }
+        let (left, right) = self.split::<NativeFloat>(0.5);
+        left.flatten_recursive(tolerance, depth + 1, points);
+        right.flatten_recursive(tolerance, depth + 1, points);
+    }
+
+
+    /// Returns the vertices of the convex hull of the control points in counter-clockwise order,
+    /// starting at the control point with the smallest x (and y) coordinate.
+    /// As the curve lies within the convex hull of its control points,
+    /// this is a tighter bound than the (axis aligned) bounding box.
+    /// Control points on the edges of the hull and duplicates are excluded.
+    /// Uses Andrew's monotone chain algorithm, only the first two axes of the points are used.
+    pub fn convex_hull(&self) -> ArrayVec<[P; N]> {
+        debug_assert!(P::DIM == 2);
+        let mut points = self.control_points;
+        points.sort_unstable_by(|a, b| {
+            (a.axis(0), a.axis(1)).partial_cmp(&(b.axis(0), b.axis(1))).unwrap()
+        });
+        // the cross product is positive if o -> a -> b turns counter-clockwise
+        let cross = |o: P, a: P, b: P| (a - o).cross_2d(&(b - o));
+
+        let mut hull: ArrayVec<[P; N]> = ArrayVec::new();
+        // lower hull from left to right
+        for (i, &p) in points.iter().enumerate() {
+            if i > 0 && p == points[i-1] {
+                continue;
+            }
+            while hull.len() >= 2 && cross(hull[hull.len()-2], hull[hull.len()-1], p) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        // upper hull from right to left, the first point closes the hull but is not repeated
+        let lower_len = hull.len();
+        for i in (0..N-1).rev() {
+            let p = points[i];
+            if p == points[i+1] {
+                continue;
+            }
+            while hull.len() > lower_len && cross(hull[hull.len()-2], hull[hull.len()-1], p) <= 0.0 {
+                hull.pop();
+            }
+            if i > 0 {
+                hull.push(p);
+            }
+        }
+        hull
+    }
+
+
     /// Returns the derivative curve of self which has N-1 control points.
     /// The derivative of an nth degree Bézier curve is an (n-1)th degree Bézier curve, 
     /// with one fewer term, and new weights w0...wn-1 derived from the 
-    /// original weights as n(wi+1 - wi). So for a 3rd degree curve, with four weights, 
+    /// original weights as n(wi+1 - wi), where n = N-1 is the degree. So for a 3rd degree curve, with four weights, 
     /// the derivative has three new weights: 
     ///     w0 = 3(w1-w0), w'1 = 3(w2-w1) and w'2 = 3(w3-w2). 
+    /// The curve needs at least two control points (N >= 2, a line), which is enforced by the bound
+    /// `[(); N-2]: Sized`. Calling it for N < 2 fails to compile with an overflow in `N-2`
+    /// and generic code calling derivative() has to repeat the bound.
     pub fn derivative<F>(&self) -> Bezier<P, {N-1}>
     where
     F: Float,
@@ -114,24 +552,24 @@ P: Add + Sub + Copy
     NativeFloat: Sub<F, Output = F> 
         + Add<F, Output = F>
         + Mul<F, Output = F>
-        + Into<F>
+        + Into<F>,
+    [(); N-2]: Sized,
     {
         let mut new_points: [P; N-1] = [P::default(); N-1]; 
-        for (i, _) in self.control_points.iter().enumerate() {
-            new_points[i] = (self.control_points[i+1] - self.control_points[i]) * (N as NativeFloat);
-            if i == self.control_points.len()-2 {
-                break;
-            }
+        for i in 0..N-1 {
+            new_points[i] = (self.control_points[i+1] - self.control_points[i]) * ((N-1) as NativeFloat);
         }
         return Bezier::new(new_points)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "f32")))]
 mod tests 
 {
     use super::*;
     use super::point_generic::PointN;
+    use super::cubic_bezier::CubicBezier;
+    use super::quadratic_bezier::QuadraticBezier;
     //use crate::num_traits::{Pow};
     #[test]
     fn eval_endpoints() {
@@ -185,8 +623,7 @@ mod tests
         // compare left and right subcurves with parent curve
         // this is tricky as we have to map t->t/2 (for left) which will 
         // inevitably contain rounding errors from floating point ops.
-        // instead, take the difference of the two points which must not exceed the absolute error
-        // TODO update test to use norm() instead, once implemented for Point
+        // instead, take the distance of the two points which must not exceed the absolute error
         let max_err = 1e-14;
         let nsteps: usize =  1000;                                      
         for t in 0..=nsteps {
@@ -199,17 +636,433 @@ mod tests
             // left
 
             // check the left part of the split curve
-            let mut err = bezier.eval(t/2.0) - left.eval(t);
-            //dbg!(err);
+            assert!(bezier.eval(t/2.0).distance(&left.eval(t)) < max_err);
+            // check the right part of the split curve
+            assert!(bezier.eval((t*0.5)+0.5).distance(&right.eval(t)) < max_err);
+        }
+    }
+
+
+    #[test]
+    fn arclen_cubic_equivalence() {
+        // a generic bezier of degree 3 must have the same arc length as the specialized cubic type
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64])];
+        let bezier: Bezier<PointN<f64, 2>, 4> = Bezier::new(points);
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+
+        let polygon_len = bezier.control_polygon_length::<f64>();
+        let chord_len = bezier.chord_length::<f64>();
+        assert!((polygon_len - cubic.control_polygon_length::<f64>()).abs() < 1e-12);
+        assert!((chord_len - cubic.chord_length::<f64>()).abs() < 1e-12);
+
+        let max_err = 1e-9;
+        for &nsteps in [10, 100, 1000].iter() {
+            let arclen = bezier.arclen::<f64>(nsteps);
+            assert!((arclen - cubic.arclen::<f64>(nsteps)).abs() < max_err);
+            // the polyline can never be longer than the control polygon or shorter than the chord
+            assert!(arclen < polygon_len && arclen > chord_len);
+        }
+    }
+
+
+    #[test]
+    fn bounding_box_contains() {
+        // check if bounding box for a quintic contains all points (with some approximation error)
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64])];
+        let curve: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        let bounds = curve.bounding_box::<f64>();
+
+        let max_err = 1e-9;
+        let mut sampled = [(f64::MAX, f64::MIN); 2];
+        let nsteps: usize = 1000;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = curve.eval(t);
+            for (idx, axis) in p.into_iter().enumerate() {
+                assert!( (axis >= (bounds[idx].0 - max_err)) && (axis <= (bounds[idx].1 + max_err)) );
+                sampled[idx] = (sampled[idx].0.min(axis), sampled[idx].1.max(axis));
+            }
+        }
+        // the box must also be tight, i.e. not much larger than the sampled extrema
+        for idx in 0..2 {
+            assert!( (bounds[idx].0 - sampled[idx].0).abs() < 1e-4 );
+            assert!( (bounds[idx].1 - sampled[idx].1).abs() < 1e-4 );
+        }
+        // and it must be tighter than the control points in y, where the curve doesn't reach -4
+        assert!( bounds[1].0 > -4.0 );
+    }
+
+
+    #[test]
+    fn derivative_cubic_equivalence() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64])];
+        let bezier: Bezier<PointN<f64, 2>, 4> = Bezier::new(points);
+        let derivative = bezier.derivative::<f64>();
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        let cubic_derivative = cubic.derivative::<f64>();
+
+        let max_err = 1e-12;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            // analytic derivative of the cubic bernstein form:
+            // B'(t) = 3(1-t)^2 (P1-P0) + 6(1-t)t (P2-P1) + 3t^2 (P3-P2)
+            let analytic = (points[1] - points[0]) * (3.0 * (1.0-t) * (1.0-t))
+                            + (points[2] - points[1]) * (6.0 * (1.0-t) * t)
+                            + (points[3] - points[2]) * (3.0 * t * t);
+            let err = derivative.eval(t) - analytic;
             for axis in err {
                 assert!(axis.abs() < max_err);
             }
-            // check the right part of the split curve
-            err = bezier.eval((t*0.5)+0.5) - right.eval(t);
-            //dbg!(err);
+            let err = derivative.eval(t) - cubic_derivative.eval(t);
             for axis in err {
                 assert!(axis.abs() < max_err);
             }
         }
     }
-}
\ No newline at end of file
+
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let json = serde_json::to_string(&bezier).unwrap();
+        let deserialized: Bezier<PointN<f64, 2>, 5> = serde_json::from_str(&json).unwrap();
+        for (p, q) in bezier.into_iter().zip(deserialized.into_iter()) {
+            assert!(p == q);
+        }
+    }
+
+
+    #[test]
+    fn reverse() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let reversed = bezier.reverse();
+        for (p, q) in reversed.reverse().into_iter().zip(bezier.into_iter()) {
+            assert!(p == q);
+        }
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(reversed.eval(t).distance(&bezier.eval(1.0 - t)) < max_err);
+        }
+    }
+
+
+    #[test]
+    fn iter_uniform() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let nsteps: usize = 10;
+        assert!(bezier.iter_uniform::<f64>(nsteps).count() == nsteps + 1);
+        assert!(bezier.iter_uniform::<f64>(nsteps).next() == Some(points[0]));
+        assert!(bezier.iter_uniform::<f64>(nsteps).last() == Some(points[4]));
+        for (i, p) in bezier.iter_uniform::<f64>(nsteps).enumerate() {
+            let t = i as f64 * 1f64/(nsteps as f64);
+            assert!(p.distance(&bezier.eval(t)) < EPSILON);
+        }
+    }
+
+
+    /// Checks the number of control points and the values of the derivative
+    /// against central differences for the degrees 1 to 4
+    #[test]
+    fn derivative_degrees() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64])];
+
+        fn check<const N: usize>(bezier: Bezier<PointN<f64, 2>, N>)
+        where [(); N-2]: Sized, [(); N-1]: Sized {
+            let derivative = bezier.derivative::<f64>();
+            assert!(derivative.control_points.len() == N - 1);
+            let h = 1e-6;
+            let max_err = 1e-6;
+            let nsteps: usize = 100;
+            for t in 1..nsteps {
+                let t = t as f64 * 1f64/(nsteps as f64);
+                let difference = (bezier.eval(t + h) - bezier.eval(t - h)) * (0.5 / h);
+                assert!(derivative.eval(t).distance(&difference) < max_err);
+            }
+        }
+        check(Bezier::new([points[0], points[1]]));
+        check(Bezier::new([points[0], points[1], points[2]]));
+        check(Bezier::new([points[0], points[1], points[2], points[3]]));
+        check(Bezier::new(points));
+        // the derivative of a line is constant
+        let line = Bezier::new([points[0], points[1]]).derivative::<f64>();
+        assert!(line.control_points[0] == points[1] - points[0]);
+    }
+
+
+    #[test]
+    fn elevate_and_reduce() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let elevated = bezier.elevate();
+        assert!(elevated.control_points.len() == 6);
+        let max_err = 1e-12;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(elevated.eval(t).distance(&bezier.eval(t)) < max_err);
+        }
+        // reducing an elevated curve yields the original
+        let (reduced, err): (Bezier<PointN<f64, 2>, 5>, f64) = elevated.reduce();
+        assert!(err < max_err);
+        for (p, q) in reduced.into_iter().zip(bezier.into_iter()) {
+            assert!(p.distance(&q) < max_err);
+        }
+        // also for an even number of reduced control points
+        let cubic: Bezier<PointN<f64, 2>, 4> = Bezier::new([points[0], points[1], points[2], points[3]]);
+        let (reduced, err): (Bezier<PointN<f64, 2>, 4>, f64) = cubic.elevate().reduce();
+        assert!(err < max_err);
+        for (p, q) in reduced.into_iter().zip(cubic.into_iter()) {
+            assert!(p.distance(&q) < max_err);
+        }
+        // a general curve is only approximated, like for CubicBezier::reduce()
+        let (reduced, err): (Bezier<PointN<f64, 2>, 3>, f64) = cubic.reduce();
+        let (quadratic, quadratic_err): (QuadraticBezier<PointN<f64, 2>>, f64) =
+            CubicBezier::new(points[0], points[1], points[2], points[3]).reduce();
+        assert!(err > 0.1 && (err - quadratic_err).abs() < max_err);
+        assert!(reduced.control_points[1].distance(&quadratic.ctrl) < max_err);
+    }
+
+
+    #[test]
+    fn convex_hull() {
+        let points = [
+            PointN::new([0f64, 0f64]),
+            PointN::new([4f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([4f64, 4f64]),
+            PointN::new([0f64, 4f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let hull = bezier.convex_hull();
+        // the interior point (1,1) is excluded
+        assert!(hull.len() == 4);
+        assert!(hull[0] == points[0] && hull[1] == points[1] && hull[2] == points[3] && hull[3] == points[4]);
+        // the hull is counter-clockwise
+        for i in 0..hull.len() {
+            let (a, b, c) = (hull[i], hull[(i+1) % hull.len()], hull[(i+2) % hull.len()]);
+            assert!((b - a).cross_2d(&(c - b)) > 0.0);
+        }
+        // all points of the curve lie within the hull
+        for p in bezier.iter_uniform::<f64>(100) {
+            for i in 0..hull.len() {
+                let (a, b) = (hull[i], hull[(i+1) % hull.len()]);
+                assert!((b - a).cross_2d(&(p - a)) >= 0.0);
+            }
+        }
+
+        // colinear and duplicate points
+        let line: Bezier<PointN<f64, 2>, 4> = Bezier::new([
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([3f64, 3f64])]);
+        let hull = line.convex_hull();
+        assert!(hull.len() == 2);
+        assert!(hull[0] == PointN::new([0f64, 0f64]) && hull[1] == PointN::new([3f64, 3f64]));
+        let point: Bezier<PointN<f64, 2>, 3> = Bezier::new([PointN::new([1f64, 2f64]); 3]);
+        assert!(point.convex_hull().len() == 1);
+    }
+
+
+    #[test]
+    fn is_linear() {
+        let tolerance = 1e-9;
+        let mut points = [PointN::new([0f64; 2]); 6];
+        for i in 0..6 {
+            // unevenly spaced points on a line, one beyond the end point
+            let t = [0.0, 0.3, 1.2, 0.1, 0.7, 1.0][i];
+            points[i] = PointN::new([1f64, 2f64]) + PointN::new([3f64, -1f64]) * t;
+        }
+        // the control point beyond the end point deviates from the segment
+        let overshooting: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        assert!(!overshooting.is_linear(tolerance));
+        points[2] = PointN::new([1f64, 2f64]) + PointN::new([3f64, -1f64]) * 0.5;
+        let straight: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        assert!(straight.is_linear(tolerance));
+        points[3] = points[3] + PointN::new([0f64, 0.1f64]);
+        let bent: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        assert!(!bent.is_linear(tolerance));
+        assert!(bent.is_linear(0.1));
+
+        // degenerate baseline
+        let closed: Bezier<PointN<f64, 2>, 4> = Bezier::new([
+            PointN::new([0f64, 0f64]), PointN::new([1f64, 1f64]),
+            PointN::new([1f64, 0f64]), PointN::new([0f64, 0f64])]);
+        assert!(!closed.is_linear(tolerance));
+        let point: Bezier<PointN<f64, 2>, 3> = Bezier::new([PointN::new([1f64, 2f64]); 3]);
+        assert!(point.is_linear(tolerance));
+
+        // no inner control points
+        let single: Bezier<PointN<f64, 2>, 1> = Bezier::new([PointN::new([1f64, 2f64])]);
+        assert!(single.is_linear(tolerance));
+        let segment: Bezier<PointN<f64, 2>, 2> = Bezier::new([
+            PointN::new([0f64, 0f64]), PointN::new([1f64, 1f64])]);
+        assert!(segment.is_linear(tolerance));
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flattened_within_tolerance() {
+        let points = [
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 3f64]),
+            PointN::new([2f64, -3f64]),
+            PointN::new([3f64, 3f64]),
+            PointN::new([4f64, -3f64]),
+            PointN::new([5f64, 0f64])];
+        let bezier: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        let tolerance = 1e-3;
+        let polyline = bezier.flattened(tolerance);
+        assert!(polyline.len() > 2);
+        assert!(polyline[0] == points[0] && polyline[polyline.len()-1] == points[5]);
+        // every sampled point of the curve is within tolerance of the polyline
+        let nsteps: usize = 1000;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = bezier.eval(t);
+            let dist = polyline.windows(2)
+                        .map(|w| LineSegment::new(w[0], w[1]).distance_to_point::<f64>(p))
+                        .fold(f64::INFINITY, |a, b| a.min(b));
+            assert!(dist <= tolerance);
+        }
+    }
+
+
+    fn assert_horner_equivalence<const N: usize>(points: [PointN<f64, 2>; N]) {
+        let bezier = Bezier::new(points);
+        let max_err = 1e-13;
+        let nsteps: usize = 1000;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(bezier.eval_horner(t).distance(&bezier.eval(t)) < max_err);
+        }
+        assert!(bezier.eval_horner(0.0) == points[0]);
+        assert!(bezier.eval_horner(1.0) == points[N - 1]);
+    }
+
+    #[test]
+    fn eval_horner() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64]),
+                PointN::new([9.5f64, -2.3f64])];
+        // degrees 1 to 6
+        assert_horner_equivalence([points[0], points[1]]);
+        assert_horner_equivalence([points[0], points[1], points[2]]);
+        assert_horner_equivalence([points[0], points[1], points[2], points[3]]);
+        assert_horner_equivalence([points[0], points[1], points[2], points[3], points[4]]);
+        assert_horner_equivalence([points[0], points[1], points[2], points[3], points[4], points[5]]);
+        assert_horner_equivalence(points);
+    }
+
+
+    #[test]
+    fn arclen_adaptive() {
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64])];
+        let bezier: Bezier<PointN<f64, 2>, 4> = Bezier::new(points);
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        let reference = cubic.arclen_gauss::<f64>(1e-12);
+        for &tolerance in [1e-3, 1e-6, 1e-9].iter() {
+            assert!((bezier.arclen_adaptive::<f64>(tolerance) - reference).abs() < tolerance);
+        }
+        // the degree elevated quintic is the same curve
+        let quintic = bezier.elevate().elevate();
+        assert!((quintic.arclen_adaptive::<f64>(1e-9) - reference).abs() < 1e-9);
+
+        // a straight quintic with unevenly spaced control points has the length of its chord
+        let line: Bezier<PointN<f64, 2>, 6> = Bezier::new([
+            PointN::new([0f64, 0f64]),
+            PointN::new([0.4f64, 0.3f64]),
+            PointN::new([2f64, 1.5f64]),
+            PointN::new([2.4f64, 1.8f64]),
+            PointN::new([3.6f64, 2.7f64]),
+            PointN::new([4f64, 3f64])]);
+        assert!((line.arclen_adaptive::<f64>(1e-9) - 5.0).abs() < 1e-9);
+
+        // a proper quintic against the limit of the polyline approximation
+        let quintic = Bezier::new([
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([7.3f64, 2.7f64]),
+            PointN::new([8.9f64, 1.7f64])]);
+        let arclen = quintic.arclen_adaptive::<f64>(1e-9);
+        assert!((arclen - quintic.arclen::<f64>(100000)).abs() < 1e-7);
+        assert!(arclen < quintic.control_polygon_length::<f64>() && arclen > quintic.chord_length::<f64>());
+    }
+
+
+    #[test]
+    fn control_box_contains_bounding_box() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64])];
+        let curve: Bezier<PointN<f64, 2>, 6> = Bezier::new(points);
+        let bounds = curve.bounding_box::<f64>();
+        let control = curve.control_box::<f64>();
+        for dim in 0..2 {
+            assert!(control[dim].0 <= bounds[dim].0 && bounds[dim].1 <= control[dim].1);
+        }
+        assert!(control[0] == (0.0, 8.9));
+        assert!(control[1] == (-4.0, 3.0));
+    }
+}
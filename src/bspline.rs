@@ -2,6 +2,11 @@ use core::slice::*;
 
 use super::*;
 use super::point::Point;
+#[cfg(feature = "alloc")]
+use super::bezier::Bezier;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// General Implementation of a BSpline with choosable degree, control points and knots.
 /// Generic parameters:
@@ -36,7 +41,8 @@ F: Float + Into<NativeFloat>
 {
     /// Create a new B-spline curve that interpolates
     /// the `control_points` using a piecewise polynomial of `degree` within intervals specified by the `knots`. 
-    /// The knots _must_ be sorted in non-decreasing order, the constructor enforces this which may yield undesired results. 
+    /// The knots _must_ be sorted in non-decreasing order (as required for the binary search of the knot span),
+    /// otherwise the constructor returns None. Use `new_sorted` to sort them instead.
     /// The degree is defined as `curve_order - 1`.
     /// Desired curve must have a valid number of control points and knots in relation to its degree or the constructor will return None. 
     /// A B-Spline curve requires at least one more control point than the degree (`control_points.len() >
@@ -50,15 +56,51 @@ F: Float + Into<NativeFloat>
             // panic!(format!("Invalid number of knots, got {}, expected {}", knots.len(),
             //     control_points.len() + degree + 1));
             None
+        }
+        else if knots.iter().any(|k| k.is_nan()) || knots.windows(2).any(|w| w[0] > w[1]) {
+            None
         } else {
-            // TODO force sorting of the knots required for binary search (knot span) -> mutable reference required
-            // FIX maybe dont sort and just use linear search for knot span, as knot vectors wont be really large anyway
-            //.sort_by(|a, b| a.partial_cmp(b).unwrap());
             Some(BSpline { control_points, knots, degree })
         }
         
     }
 
+    /// Like `new`, but sorts a copy of the knots into non-decreasing order first.
+    /// Returns None for invalid numbers of control points or knots and if any knot is NaN.
+    pub fn new_sorted(control_points: [P; C], knots: [F; K], degree: usize) -> Option< BSpline<P, F, {C}, {K}, {O}> > {
+        // without NaN the knots are totally ordered
+        if knots.iter().any(|k| k.is_nan()) {
+            return None
+        }
+        let mut sorted = knots;
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        BSpline::new(control_points, sorted, degree)
+    }
+
+    /// Create a closed (periodic) B-spline curve of `degree` through the loop of `points`,
+    /// which is C^(degree-1) continuous everywhere including the seam.
+    /// The first degree points are appended to the control points (so C = N + degree)
+    /// and the knots are uniform 0, 1, ..., K-1. The knot domain is [degree, degree + N], the curve
+    /// repeats with the period N, see eval_periodic().
+    /// Returns None if C and K don't match N and the degree or if there are not more points than the degree.
+    pub fn new_periodic<const N: usize>(points: [P; N], degree: usize) -> Option< BSpline<P, F, {C}, {K}, {O}> >
+    where
+    NativeFloat: Into<F>
+    {
+        if N <= degree || C != N + degree {
+            return None
+        }
+        let mut control_points: [P; C] = [points[0]; C];
+        for i in 0..C {
+            control_points[i] = points[i % N];
+        }
+        let mut knots: [F; K] = [F::zero(); K];
+        for (i, knot) in knots.iter_mut().enumerate() {
+            *knot = (i as NativeFloat).into();
+        }
+        BSpline::new(control_points, knots, degree)
+    }
+
     /// Compute a point on the curve at `t`, the parameter **must** be in the inclusive range
     /// of values returned by `knot_domain`. If `t` is out of bounds this function will assert
     /// on debug builds and on release builds you'll likely get an out of bounds crash.
@@ -78,6 +120,20 @@ F: Float + Into<NativeFloat>
     }
 
 
+    /// Evaluates a closed curve created by new_periodic() at any t, which is wrapped into the knot domain
+    /// with the period of its length, so that evaluating past the end continues at the start.
+    /// For other curves the result is not meaningful outside of the domain.
+    pub fn eval_periodic(&self, t: F) -> P {
+        let (kmin, kmax) = self.knot_domain();
+        let period = kmax - kmin;
+        let mut t = (t - kmin) % period;
+        if t < F::zero() {
+            t = t + period;
+        }
+        self.eval(kmin + t)
+    }
+
+
     /// Returns an iterator over the control points.
     pub fn control_points(&self) -> Iter<'_, P>  {
         self.control_points.iter()
@@ -97,6 +153,169 @@ F: Float + Into<NativeFloat>
     }
 
 
+    /// Returns a bounding box of the curve as an array of (min, max) tuples for each dimension.
+    /// This is the bounding box of the control points: the curve lies in the convex hull of its
+    /// control points, so the box always contains it, but it is not tight in general
+    /// (unlike CubicBezier::bounding_box(), no extrema are computed).
+    pub fn bounding_box(&self) -> [(F, F); P::DIM]
+    where
+    NativeFloat: Into<F>
+    {
+        return point::bounding_box(&self.control_points)
+    }
+
+
+    /// Returns the Greville abscissae, i.e. for each control point the average of the degree knots
+    /// u_(i+1), ..., u_(i+p) following its first knot. They are the parameters at which the control points
+    /// have the largest influence and all lie in the knot domain. A curve whose control points are placed
+    /// at their Greville abscissae on a line is the linear function t (linear precision).
+    /// For degree 0 the midpoints of the knot spans are returned instead.
+    #[cfg(feature = "alloc")]
+    pub fn greville(&self) -> Vec<F>
+    where
+    NativeFloat: Into<F>
+    {
+        let p = self.degree;
+        (0..C).map(|i| {
+            if p == 0 {
+                return (self.knots[i] + self.knots[i + 1]) * 0.5.into()
+            }
+            let sum = self.knots[i + 1..=i + p].iter().fold(F::zero(), |acc, u| acc + *u);
+            sum / (p as NativeFloat).into()
+        }).collect()
+    }
+
+
+    /// Returns the derivative curve of self, which is a B-spline of degree p-1 (order O-1)
+    /// with one control point less and the first and last knots dropped.
+    /// Its control points are
+    ///     Q_i = p * (P_(i+1) - P_i) / (u_(i+p+1) - u_(i+1))
+    /// where p is the degree, P_i the control points and u_i the knots of the original curve.
+    /// For zero-width knot spans (repeated knots) the coefficient is treated as zero.
+    /// Returns None for a curve of degree 0: it is piecewise constant, and with the same sizes
+    /// its derivative couldn't be a valid B-spline (which needs one more knot per control point).
+    pub fn derivative(&self) -> Option<BSpline<P, F, {C-1}, {K-2}, {O-1}>> {
+        if self.degree == 0 {
+            return None
+        }
+        let mut control_points: [P; C-1] = [P::default(); C-1];
+        for i in 0..C-1 {
+            let span = self.knots[i + self.degree + 1] - self.knots[i + 1];
+            if span.into() > EPSILON {
+                let factor = (self.degree as NativeFloat) / span.into();
+                control_points[i] = (self.control_points[i+1] - self.control_points[i]) * factor;
+            }
+        }
+        let mut knots: [F; K-2] = [self.knots[0]; K-2];
+        knots.copy_from_slice(&self.knots[1..K-1]);
+
+        Some(BSpline { control_points, knots, degree: self.degree - 1 })
+    }
+
+
+    /// Splits the curve at t (strictly inside the knot domain) into the two curves on [kmin, t] and [t, kmax].
+    /// The knot t is inserted (Boehm's algorithm) until its multiplicity equals the degree, so that the
+    /// control point at t is shared by both halves. The left half keeps the knots up to t and ends with
+    /// degree + 1 knots at t, the right half starts with degree + 1 knots at t and keeps the knots after it.
+    /// The sizes of the halves depend on the number of knots before and after t and on its multiplicity,
+    /// they are given by the const generic parameters (CL control points and KL = CL + O knots for the left half
+    /// and CR, KR for the right one). Returns None if they don't match, if t is not strictly inside the domain
+    /// or if its multiplicity exceeds the degree (the curve is already split there).
+    /// E.g. splitting a cubic with the knots [0, 0, 0, 0, 1, 2, 2, 2, 2] at t = 1 yields two halves with
+    /// 4 control points and 8 knots each.
+    pub fn split<const CL: usize, const KL: usize, const CR: usize, const KR: usize>(&self, t: F)
+        -> Option<(BSpline<P, F, {CL}, {KL}, {O}>, BSpline<P, F, {CR}, {KR}, {O}>)>
+    where
+    [(); C + O]:,
+    [(); K + O]:,
+    {
+        let p = self.degree;
+        let (kmin, kmax) = self.knot_domain();
+        if !(t > kmin && t < kmax) {
+            return None
+        }
+        let multiplicity = self.knots.iter().filter(|u| **u == t).count();
+        if multiplicity > p {
+            return None
+        }
+        // at most p knots are inserted, which adds one control point and knot each
+        let mut points: [P; C + O] = [P::default(); C + O];
+        points[..C].copy_from_slice(&self.control_points);
+        let mut knots: [F; K + O] = [t; K + O];
+        knots[..K].copy_from_slice(&self.knots);
+        let mut n_points = C;
+        let mut n_knots = K;
+        // index of the last knot <= t, which is at least p as t > kmin
+        let mut k = self.knots.iter().rposition(|u| *u <= t).unwrap();
+
+        for _ in multiplicity..p {
+            insert_knot(&mut points, &mut knots, n_points, n_knots, k, t, p);
+            n_points += 1;
+            n_knots += 1;
+            k += 1;
+        }
+
+        // the control point at t is P_(k-p)
+        let shared = k - p;
+        if CL != shared + 1 || KL != k + 2 || CR != n_points - shared || KR != n_knots - shared {
+            return None
+        }
+        let mut left_points: [P; CL] = [P::default(); CL];
+        left_points.copy_from_slice(&points[..=shared]);
+        let mut left_knots: [F; KL] = [t; KL];
+        left_knots[..=k].copy_from_slice(&knots[..=k]);
+        let mut right_points: [P; CR] = [P::default(); CR];
+        right_points.copy_from_slice(&points[shared..n_points]);
+        let mut right_knots: [F; KR] = [t; KR];
+        right_knots[1..].copy_from_slice(&knots[shared + 1..n_knots]);
+
+        Some((BSpline { control_points: left_points, knots: left_knots, degree: p },
+              BSpline { control_points: right_points, knots: right_knots, degree: p }))
+    }
+
+
+    /// Converts the curve into a sequence of connected Bezier curves of the same degree, one for each
+    /// non-empty knot span of the domain, where the i-th curve on [u_j, u_(j+1)] is parameterized by
+    /// (t - u_j) / (u_(j+1) - u_j). Every knot in the domain (including its bounds) is inserted until its
+    /// multiplicity equals the degree, then the p+1 control points P_(j-p)..P_j of each span are its Bezier control points.
+    /// The Bezier curves have O = degree + 1 control points.
+    #[cfg(feature = "alloc")]
+    pub fn to_beziers(&self) -> Vec<Bezier<P, O>> {
+        let p = self.degree;
+        let kmax = self.knot_domain().1;
+        let mut points: Vec<P> = self.control_points.to_vec();
+        let mut knots: Vec<F> = self.knots.to_vec();
+
+        let mut i = p;
+        while i < knots.len() && knots[i] <= kmax {
+            let t = knots[i];
+            let multiplicity = knots.iter().filter(|u| **u == t).count();
+            for _ in multiplicity..p {
+                // the span left of kmax is used to insert it, as no span starts at kmax
+                let k = knots.iter().rposition(|u| *u <= t && *u < kmax).unwrap();
+                points.push(P::default());
+                knots.push(t);
+                let (n_points, n_knots) = (points.len() - 1, knots.len() - 1);
+                insert_knot(&mut points, &mut knots, n_points, n_knots, k, t, p);
+            }
+            // continue with the next distinct knot
+            while i < knots.len() && knots[i] == t {
+                i += 1;
+            }
+        }
+
+        let mut beziers = Vec::new();
+        for j in p..knots.len() - p - 1 {
+            if knots[j] < knots[j + 1] {
+                let mut control_points: [P; O] = [P::default(); O];
+                control_points.copy_from_slice(&points[j - p..=j]);
+                beziers.push(Bezier::new(control_points));
+            }
+        }
+        beziers
+    }
+
+
     /// Iteratively compute de Boor's B-spline algorithm, this computes the recursive
     /// de Boor algorithm tree from the bottom up. At each level we use the results
     /// from the previous one to compute this level and store the results in the
@@ -107,7 +326,7 @@ F: Float + Into<NativeFloat>
         let mut tmp: [P; O] = [P::default(); O];
         for j in 0..=self.degree {
             let p = j + start_knot - self.degree - 1;
-            tmp[p] = self.control_points[p];
+            tmp[j] = self.control_points[p];
         }
         for lvl in 0..self.degree {
             let k = lvl + 1;
@@ -115,7 +334,7 @@ F: Float + Into<NativeFloat>
                 let i = j + k + start_knot - self.degree;
                 let alpha = (t - self.knots[i - 1]) / (self.knots[i + self.degree - k] - self.knots[i - 1]);
                 debug_assert!(!alpha.is_nan());
-                tmp[j] = tmp[j] * (1.0 - alpha.into()) + tmp[j + 1] * alpha.into();
+                tmp[j] = tmp[j].interpolate(&tmp[j + 1], alpha.into());
             }
         }
         tmp[0]
@@ -176,7 +395,71 @@ F: Float + Into<NativeFloat>
 }
 
 
-#[cfg(test)]
+/// Returns the uniform knot vector 0, 1, ..., n_control + degree for a B-spline with n_control control points
+/// of the given degree. The curve doesn't interpolate its first and last control points, its knot domain is
+/// [degree, n_control]. Returns an empty vector if there are not more control points than the degree.
+#[cfg(feature = "alloc")]
+pub fn uniform_knots<F>(n_control: usize, degree: usize) -> Vec<F>
+where
+F: Float,
+NativeFloat: Into<F>
+{
+    if n_control <= degree {
+        return Vec::new()
+    }
+    (0..n_control + degree + 1).map(|i| (i as NativeFloat).into()).collect()
+}
+
+/// Returns the clamped (open uniform) knot vector for a B-spline with n_control control points of the given degree:
+/// degree + 1 zeros, the interior knots 1, 2, ..., n_control - degree - 1 and degree + 1 times n_control - degree.
+/// The curve interpolates its first and last control points at the bounds of its knot domain [0, n_control - degree].
+/// Returns an empty vector if there are not more control points than the degree.
+#[cfg(feature = "alloc")]
+pub fn clamped_knots<F>(n_control: usize, degree: usize) -> Vec<F>
+where
+F: Float,
+NativeFloat: Into<F>
+{
+    if n_control <= degree {
+        return Vec::new()
+    }
+    let last = n_control - degree;
+    (0..n_control + degree + 1)
+        .map(|i| (i.saturating_sub(degree).min(last) as NativeFloat).into())
+        .collect()
+}
+
+
+/// Inserts the knot t after index k (knots[k] <= t <= knots[k+1] with knots[k] < knots[k+1] and k >= p)
+/// into the first n_knots knots and updates the first n_points control points with Boehm's algorithm.
+/// The slices need room for one more control point and knot.
+fn insert_knot<P, F>(points: &mut [P], knots: &mut [F], n_points: usize, n_knots: usize, k: usize, t: F, p: usize)
+where
+P: Add + Sub + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>
+    + Point<Scalar = NativeFloat>,
+F: Float + Into<NativeFloat>
+{
+    // Q_i = P_(i-1) for i > k
+    for i in (k + 1..=n_points).rev() {
+        points[i] = points[i - 1];
+    }
+    // Q_i = (1 - a_i) * P_(i-1) + a_i * P_i with a_i = (t - u_i) / (u_(i+p) - u_i) for k-p < i <= k,
+    // descending so that P_(i-1) is not overwritten yet
+    for i in (k + 1 - p..=k).rev() {
+        let a = (t - knots[i]) / (knots[i + p] - knots[i]);
+        points[i] = points[i - 1].interpolate(&points[i], a.into());
+    }
+    for j in (k + 1..=n_knots).rev() {
+        knots[j] = knots[j - 1];
+    }
+    knots[k + 1] = t;
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
 mod tests 
 {
     //use std;
@@ -211,4 +494,347 @@ mod tests
         // for now only check if has positive arclen
         assert!(curve.arclen(100) > 0.);
     }
-}
\ No newline at end of file
+
+
+    #[test]
+    fn knot_order() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64])];
+        let unsorted: [f64; 8] = [0., 0., 1., 0., 2., 3., 3., 3.];
+        let b: Option<BSpline<PointN<f64, 2>, f64, 4, 8, 4 >> = BSpline::new(points, unsorted, 3);
+        assert!(b.is_none());
+
+        let curve: BSpline<PointN<f64, 2>, f64, 4, 8, 4> = BSpline::new_sorted(points, unsorted, 3).unwrap();
+        let sorted: [f64; 8] = [0., 0., 0., 1., 2., 3., 3., 3.];
+        assert!(curve.knots().zip(sorted.iter()).all(|(a, b)| a == b));
+        let reference: BSpline<PointN<f64, 2>, f64, 4, 8, 4> = BSpline::new(points, sorted, 3).unwrap();
+        assert!(curve.eval(1.5) == reference.eval(1.5));
+
+        let nan: [f64; 8] = [0., 0., 0., f64::NAN, 2., 3., 3., 3.];
+        let b: Option<BSpline<PointN<f64, 2>, f64, 4, 8, 4 >> = BSpline::new(points, nan, 3);
+        assert!(b.is_none());
+        let b: Option<BSpline<PointN<f64, 2>, f64, 4, 8, 4 >> = BSpline::new_sorted(points, nan, 3);
+        assert!(b.is_none());
+    }
+
+
+    #[test]
+    fn eval_reference_values() {
+        let points = [
+                PointN::new([0f64, 0f64]),
+                PointN::new([1f64, 2f64]),
+                PointN::new([3f64, 2f64]),
+                PointN::new([4f64, 0f64])];
+        let max_err = 1e-14;
+
+        // uniform cubic b-spline: the only polynomial piece lives on the knot span [3, 4]
+        // and blends the control points with the uniform basis functions:
+        // t=3:   (P0 + 4*P1 + P2) / 6
+        // t=3.5: (P0 + 23*P1 + 23*P2 + P3) / 48
+        // t=4:   (P1 + 4*P2 + P3) / 6
+        let knots: [f64; 8] = [0., 1., 2., 3., 4., 5., 6., 7.];
+        let curve: BSpline<PointN<f64, 2>, f64, 4, 8, 4> = BSpline::new(points, knots, 3).unwrap();
+        assert!(curve.knot_domain() == (3.0, 4.0));
+        let expected = [
+            (3.0, PointN::new([7.0/6.0, 10.0/6.0])),
+            (3.5, PointN::new([2.0, 92.0/48.0])),
+            (4.0, PointN::new([17.0/6.0, 10.0/6.0])),
+        ];
+        for (t, p) in expected.iter() {
+            let err = curve.eval(*t) - *p;
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+
+        // with one more control point there is a second span [4, 5] which blends P1..P4
+        let points5 = [points[0], points[1], points[2], points[3], PointN::new([6f64, 3f64])];
+        let knots: [f64; 9] = [0., 1., 2., 3., 4., 5., 6., 7., 8.];
+        let curve: BSpline<PointN<f64, 2>, f64, 5, 9, 4> = BSpline::new(points5, knots, 3).unwrap();
+        assert!(curve.knot_domain() == (3.0, 5.0));
+        let expected = [
+            (3.5, PointN::new([2.0, 92.0/48.0])),
+            (4.0, PointN::new([17.0/6.0, 10.0/6.0])),
+            (4.5, PointN::new([(1.0 + 69.0 + 92.0 + 6.0)/48.0, (2.0 + 46.0 + 3.0)/48.0])),
+            (5.0, PointN::new([(3.0 + 16.0 + 6.0)/6.0, (2.0 + 3.0)/6.0])),
+        ];
+        for (t, p) in expected.iter() {
+            let err = curve.eval(*t) - *p;
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+
+        // clamped cubic b-spline with a single span is the cubic bezier curve of its control points,
+        // so it interpolates the first and last control points
+        let knots: [f64; 8] = [0., 0., 0., 0., 1., 1., 1., 1.];
+        let curve: BSpline<PointN<f64, 2>, f64, 4, 8, 4> = BSpline::new(points, knots, 3).unwrap();
+        let expected = [
+            (0.0, points[0]),
+            (0.5, PointN::new([2.0, 1.5])),
+            (1.0, points[3]),
+        ];
+        for (t, p) in expected.iter() {
+            let err = curve.eval(*t) - *p;
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+    }
+
+
+    #[test]
+    fn bounding_box_contains() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64, 3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64])];
+        let knots: [f64; 10] = [0., 0., 0., 0., 1., 1.5, 2., 2., 2., 2.];
+        let curve: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let bounds = curve.bounding_box();
+        assert!(bounds == [(0.0, 8.9), (-4.0, 3.0)]);
+
+        let (kmin, kmax) = curve.knot_domain();
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = kmin + (kmax - kmin) * (t as f64 / nsteps as f64);
+            let p = curve.eval(t);
+            for (dim, (min, max)) in bounds.iter().enumerate() {
+                assert!(p.axis(dim) >= *min && p.axis(dim) <= *max);
+            }
+        }
+    }
+
+
+    #[test]
+    fn split() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64, 3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64])];
+        let knots: [f64; 9] = [0., 0., 0., 0., 1., 2., 2., 2., 2.];
+        let curve: BSpline<PointN<f64, 2>, f64, 5, 9, 4> = BSpline::new(points, knots, 3).unwrap();
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+
+        // at the interior knot
+        let (left, right): (BSpline<_, _, 4, 8, 4>, BSpline<_, _, 4, 8, 4>) = curve.split(1.0).unwrap();
+        assert!(left.knot_domain() == (0.0, 1.0) && right.knot_domain() == (1.0, 2.0));
+        assert!(left.eval(1.0).distance(&right.eval(1.0)) < max_err);
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(left.eval(t).distance(&curve.eval(t)) < max_err);
+            assert!(right.eval(1.0 + t).distance(&curve.eval(1.0 + t)) < max_err);
+        }
+
+        // between knots, which needs one more knot insertion
+        let (left, right): (BSpline<_, _, 4, 8, 4>, BSpline<_, _, 5, 9, 4>) = curve.split(0.5).unwrap();
+        assert!(left.knot_domain() == (0.0, 0.5) && right.knot_domain() == (0.5, 2.0));
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(left.eval(0.5 * t).distance(&curve.eval(0.5 * t)) < max_err);
+            assert!(right.eval(0.5 + 1.5 * t).distance(&curve.eval(0.5 + 1.5 * t)) < max_err);
+        }
+
+        // sizes which don't match the split and parameters outside of the domain
+        assert!(curve.split::<4, 8, 5, 9>(1.0).is_none());
+        assert!(curve.split::<4, 8, 4, 8>(0.0).is_none());
+        assert!(curve.split::<4, 8, 4, 8>(2.5).is_none());
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_beziers() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64, 3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64])];
+        let max_err = 1e-13;
+        let nsteps: usize = 50;
+
+        // clamped with an interior knot of multiplicity 2 and one of multiplicity 1
+        let knots: [f64; 10] = [0., 0., 0., 0., 1., 1., 2.5, 3., 3., 3.];
+        let curve: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let beziers = curve.to_beziers();
+        assert!(beziers.len() == 2);
+        // uniform, the domain [3, 6] has three spans and its bounds are inserted as well
+        let knots: [f64; 10] = [0., 1., 2., 3., 4., 5., 6., 7., 8., 9.];
+        let uniform: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let uniform_beziers = uniform.to_beziers();
+        assert!(uniform_beziers.len() == 3);
+
+        for (curve, beziers, spans) in [(curve, beziers, [0., 1., 2.5, 3.].as_ref()), (uniform, uniform_beziers, [3., 4., 5., 6.].as_ref())].iter() {
+            for (i, bezier) in beziers.iter().enumerate() {
+                let (u0, u1) = (spans[i], spans[i + 1]);
+                for t in 0..=nsteps {
+                    let t = t as f64 * 1f64/(nsteps as f64);
+                    assert!(bezier.eval(t).distance(&curve.eval(u0 + (u1 - u0) * t)) < max_err);
+                }
+            }
+            for pair in beziers.windows(2) {
+                assert!(pair[0].eval(1.0).distance(&pair[1].eval(0.0)) < max_err);
+            }
+        }
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn knot_generators() {
+        use core::convert::TryInto;
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64, 3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64])];
+        let max_err = 1e-14;
+
+        assert!(uniform_knots::<f64>(5, 2) == [0., 1., 2., 3., 4., 5., 6., 7.]);
+        assert!(clamped_knots::<f64>(5, 2) == [0., 0., 0., 1., 2., 3., 3., 3.]);
+        assert!(clamped_knots::<f64>(5, 4) == [0., 0., 0., 0., 0., 1., 1., 1., 1., 1.]);
+        assert!(clamped_knots::<f64>(2, 2).is_empty() && uniform_knots::<f64>(2, 3).is_empty());
+
+        let linear: Option<BSpline<PointN<f64, 2>, f64, 5, 7, 2>> = BSpline::new(points, uniform_knots(5, 1).try_into().unwrap(), 1);
+        assert!(linear.unwrap().knot_domain() == (1.0, 5.0));
+        let quadratic: Option<BSpline<PointN<f64, 2>, f64, 5, 8, 3>> = BSpline::new(points, uniform_knots(5, 2).try_into().unwrap(), 2);
+        assert!(quadratic.is_some());
+
+        let linear: BSpline<PointN<f64, 2>, f64, 5, 7, 2> = BSpline::new(points, clamped_knots(5, 1).try_into().unwrap(), 1).unwrap();
+        let quadratic: BSpline<PointN<f64, 2>, f64, 5, 8, 3> = BSpline::new(points, clamped_knots(5, 2).try_into().unwrap(), 2).unwrap();
+        let cubic: BSpline<PointN<f64, 2>, f64, 5, 9, 4> = BSpline::new(points, clamped_knots(5, 3).try_into().unwrap(), 3).unwrap();
+        let quartic: BSpline<PointN<f64, 2>, f64, 5, 10, 5> = BSpline::new(points, clamped_knots(5, 4).try_into().unwrap(), 4).unwrap();
+        let ends = [
+            (linear.eval(0.0), linear.eval(4.0)),
+            (quadratic.eval(0.0), quadratic.eval(3.0)),
+            (cubic.eval(0.0), cubic.eval(2.0)),
+            (quartic.eval(0.0), quartic.eval(1.0)),
+        ];
+        for (start, end) in ends.iter() {
+            assert!(start.distance(&points[0]) < max_err);
+            assert!(end.distance(&points[4]) < max_err);
+        }
+        // the piecewise linear curve interpolates all control points
+        assert!(linear.eval(2.0).distance(&points[2]) < max_err);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn greville() {
+        let points = [PointN::new([0f64, 0f64]); 6];
+        let knots: [f64; 10] = [0., 0., 0., 0., 1., 1.5, 2., 2., 2., 2.];
+        let curve: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let abscissae = curve.greville();
+        assert!(abscissae.len() == curve.control_points().len());
+        let (kmin, kmax) = curve.knot_domain();
+        assert!(abscissae.iter().all(|g| *g >= kmin && *g <= kmax));
+        // clamped curves start and end at the bounds of the domain
+        assert!(abscissae[0] == kmin && abscissae[5] == kmax);
+        assert!((abscissae[2] - 2.5 / 3.0).abs() < 1e-15);
+
+        // linear precision: the x coordinate of the control points at their abscissae is reproduced
+        let mut points = [PointN::new([0f64, 0f64]); 6];
+        for (p, g) in points.iter_mut().zip(abscissae.iter()) {
+            p.set_axis(0, *g);
+        }
+        let line: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let nsteps: usize = 20;
+        for t in 0..=nsteps {
+            let t = 2.0 * t as f64 / nsteps as f64;
+            assert!((line.eval(t).axis(0) - t).abs() < 1e-14);
+        }
+    }
+
+
+    #[test]
+    fn periodic() {
+        // a closed loop through the corners of a square
+        let points = [
+                PointN::new([0f64, 0f64]),
+                PointN::new([1f64, 0f64]),
+                PointN::new([1f64, 1f64]),
+                PointN::new([0f64, 1f64])];
+        let curve: BSpline<PointN<f64, 2>, f64, 7, 11, 4> = BSpline::new_periodic(points, 3).unwrap();
+        let (kmin, kmax) = curve.knot_domain();
+        assert!((kmin, kmax) == (3.0, 7.0));
+        let max_err = 1e-14;
+
+        // position, tangent and second derivative match at the seam
+        assert!(curve.eval(kmin).distance(&curve.eval(kmax)) < max_err);
+        let derivative = curve.derivative().unwrap();
+        assert!(derivative.eval(kmin).distance(&derivative.eval(kmax)) < max_err);
+        let second = derivative.derivative().unwrap();
+        assert!(second.eval(kmin).distance(&second.eval(kmax)) < max_err);
+        assert!(derivative.eval(kmin).length() > 0.1);
+
+        // evaluating past the end continues at the start
+        for &dt in [0.01, 0.5, 1.0, 3.9].iter() {
+            assert!(curve.eval_periodic(kmax + dt).distance(&curve.eval(kmin + dt)) < max_err);
+            assert!(curve.eval_periodic(kmin - dt).distance(&curve.eval(kmax - dt)) < max_err);
+        }
+        // at a knot the uniform cubic blends three control points: (P0 + 4*P1 + P2) / 6
+        let seam = curve.eval(kmin);
+        assert!((seam.axis(0) - 5.0/6.0).abs() < max_err && (seam.axis(1) - 1.0/6.0).abs() < max_err);
+
+        // the sizes must match the number of points and the degree
+        let b: Option<BSpline<PointN<f64, 2>, f64, 6, 10, 4>> = BSpline::new_periodic(points, 3);
+        assert!(b.is_none());
+        let b: Option<BSpline<PointN<f64, 2>, f64, 8, 13, 5>> = BSpline::new_periodic(points, 4);
+        assert!(b.is_none());
+    }
+
+
+    #[test]
+    fn derivative_finite_difference() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64, 3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64]),
+                PointN::new([8.9f64, 1.7f64])];
+        // clamped cubic b-spline with repeated knots at both ends and an interior knot of multiplicity 2
+        let knots: [f64; 10] = [0., 0., 0., 0., 1., 1., 2., 2., 2., 2.];
+        let curve: BSpline<PointN<f64, 2>, f64, 6, 10, 4> = BSpline::new(points, knots, 3).unwrap();
+        let derivative = curve.derivative().unwrap();
+        assert!(derivative.knot_domain() == curve.knot_domain());
+
+        // compare to central differences, staying away from the domain bounds and the interior knot
+        let h = 1e-6;
+        let max_err = 1e-6;
+        for &t in [0.1, 0.3, 0.5, 0.9, 1.2, 1.5, 1.95].iter() {
+            let fd = (curve.eval(t + h) - curve.eval(t - h)) * (0.5 / h);
+            let err = derivative.eval(t) - fd;
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+    }
+
+
+    #[test]
+    fn derivative_degree_zero() {
+        // a piecewise constant curve has no derivative
+        let points = [PointN::new([0f64, 1f64]), PointN::new([2f64, 3f64]), PointN::new([4f64, 1f64])];
+        let curve: BSpline<PointN<f64, 2>, f64, 3, 4, 1> = BSpline::new(points, [0., 1., 2., 3.], 0).unwrap();
+        assert!(curve.derivative().is_none());
+        // the derivative of a linear curve has degree 0 and no further derivative
+        let linear: BSpline<PointN<f64, 2>, f64, 3, 5, 2> = BSpline::new(points, [0., 0., 1., 2., 2.], 1).unwrap();
+        let derivative = linear.derivative().unwrap();
+        assert!(derivative.eval(0.5) == PointN::new([2f64, 2f64]));
+        assert!(derivative.derivative().is_none());
+    }
+}
@@ -1,15 +1,24 @@
 use core::default::Default;
+use core::fmt;
 
 use super::*;
 use super::point::Point;
+use super::affine::Affine2;
 use super::line::LineSegment; 
 use super::quadratic_bezier::QuadraticBezier;
+use super::bezier::Bezier;
+use super::point_generic::PointN;
+use super::roots::{quadratic_roots, cubic_roots};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// A 2d  cubic Bezier curve defined by four points: the starting point, two successive
 /// control points and the ending point.
 /// The curve is defined by equation:
 /// ```∀ t ∈ [0..1],  P(t) = (1 - t)³ * start + 3 * (1 - t)² * t * ctrl1 + 3 * t² * (1 - t) * ctrl2 + t³ * end```
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CubicBezier<P>
 {
     pub (crate) start:  P,
@@ -38,6 +47,22 @@ P: Point<Scalar = NativeFloat>
         }
     }
 
+    /// Creates the cubic hermite spline segment from p0 to p1 with the tangents (derivatives) m0 at p0 and m1 at p1
+    pub fn from_hermite(p0: P, p1: P, m0: P, m1: P) -> Self {
+        CubicBezier::new(p0, p0 + m0 * (1.0 / 3.0), p1 - m1 * (1.0 / 3.0), p1)
+    }
+
+    /// Returns the curve transformed by m by applying it to each control point
+    pub fn transform(&self, m: &Affine2) -> Self {
+        CubicBezier::new(m.apply(self.start), m.apply(self.ctrl1), m.apply(self.ctrl2), m.apply(self.end))
+    }
+
+    /// Returns the same curve traversed in the opposite direction,
+    /// i.e. `self.reverse().eval(t) == self.eval(1-t)`
+    pub fn reverse(&self) -> Self {
+        CubicBezier::new(self.end, self.ctrl2, self.ctrl1, self.start)
+    }
+
     /// Evaluate a CubicBezier curve at t by direct evaluation of the polynomial (not numerically stable)
     pub fn eval<F>(&self, t: F) -> P 
     where 
@@ -55,6 +80,16 @@ P: Point<Scalar = NativeFloat>
                 + self.end * (t * t * t);
     }
 
+    /// Returns the curve in power basis for fast repeated evaluation, see CubicPoly
+    pub fn precompute(&self) -> CubicPoly<P> {
+        CubicPoly {
+            a: self.end - self.ctrl2 * 3.0 + self.ctrl1 * 3.0 - self.start,
+            b: (self.ctrl2 - self.ctrl1 * 2.0 + self.start) * 3.0,
+            c: (self.ctrl1 - self.start) * 3.0,
+            d: self.start,
+        }
+    }
+
     /// Evaluate a CubicBezier curve at t using the numerically stable De Casteljau algorithm
     pub fn eval_casteljau<F>(&self, t: F) -> P 
     where 
@@ -117,18 +152,252 @@ P: Point<Scalar = NativeFloat>
     {
         let stepsize: NativeFloat = 1.0/(nsteps as NativeFloat);
         let mut arclen: NativeFloat = 0.0;
-        for t in 1..nsteps {
+        for t in 0..nsteps {
             let t = t as NativeFloat * 1.0/(nsteps as NativeFloat);
             let p1 = self.eval_casteljau(t.into());
             let p2 = self.eval_casteljau(t.into()+stepsize.into());
 
-            arclen = arclen + (p1-p2).squared_length().sqrt();
+            arclen = arclen + p1.distance(&p2);
         
         }
         return arclen.into()
     }
 
 
+    /// Returns the length of the control polygon, which is an upper bound of the arc length
+    /// (together with chord_length() as a lower bound)
+    pub fn control_polygon_length<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let length = self.start.distance(&self.ctrl1)
+                        + self.ctrl1.distance(&self.ctrl2)
+                        + self.ctrl2.distance(&self.end);
+        return length.into()
+    }
+
+    /// Returns the distance between the start and end point, which is a lower bound of the arc length
+    pub fn chord_length<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        return self.start.distance(&self.end).into()
+    }
+
+    /// Computes the arc length of the curve by integrating the magnitude of the derivative
+    /// with adaptive Gauss-Legendre quadrature: the estimate for a parameter interval is accepted
+    /// if it differs from the sum of the estimates of its halves by less than 'tolerance',
+    /// otherwise both halves are integrated recursively with half the tolerance each.
+    /// The subdivision depth is capped at ARCLEN_MAX_DEPTH.
+    pub fn arclen_gauss<F>(&self, tolerance: F) -> F
+    where
+    F: Float
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>
+    {
+        let derivative = self.derivative::<NativeFloat>();
+        let estimate = Self::gauss_legendre(&derivative, 0.0, 1.0);
+        let arclen = Self::arclen_gauss_recursive(&derivative, 0.0, 1.0, estimate, tolerance.into(), 0);
+        return arclen.into()
+    }
+
+    fn arclen_gauss_recursive(derivative: &QuadraticBezier<P>, t0: NativeFloat, t1: NativeFloat,
+                              estimate: NativeFloat, tolerance: NativeFloat, depth: usize) -> NativeFloat
+    {
+        let mid = (t0 + t1) * 0.5;
+        let left = Self::gauss_legendre(derivative, t0, mid);
+        let right = Self::gauss_legendre(derivative, mid, t1);
+        if (left + right - estimate).abs() < tolerance || depth >= ARCLEN_MAX_DEPTH {
+            return left + right
+        }
+        Self::arclen_gauss_recursive(derivative, t0, mid, left, tolerance * 0.5, depth + 1)
+            + Self::arclen_gauss_recursive(derivative, mid, t1, right, tolerance * 0.5, depth + 1)
+    }
+
+    /// Integrates the magnitude of the derivative over [t0, t1] with 5-point Gauss-Legendre quadrature
+    fn gauss_legendre(derivative: &QuadraticBezier<P>, t0: NativeFloat, t1: NativeFloat) -> NativeFloat {
+        let half = (t1 - t0) * 0.5;
+        let center = (t0 + t1) * 0.5;
+        let mut sum = 0.0;
+        for (x, w) in GAUSS_LEGENDRE_5.iter() {
+            sum += w * derivative.eval(center + half * x).length();
+        }
+        sum * half
+    }
+
+
+    /// Returns an iterator over nsteps+1 points of the curve evaluated at equally spaced
+    /// parameters from t=0 to t=1 (inclusive) with eval_casteljau().
+    /// The first and last point are exactly the start and end point of the curve.
+    pub fn iter_uniform<F>(&self, nsteps: usize) -> impl Iterator<Item = P>
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Mul<F, Output = F>
+        + Into<F>
+    {
+        let curve = *self;
+        (0..=nsteps).map(move |i| {
+            if i == 0 {
+                curve.start
+            } else if i == nsteps {
+                curve.end
+            } else {
+                let t: F = (i as NativeFloat / nsteps as NativeFloat).into();
+                curve.eval_casteljau(t)
+            }
+        })
+    }
+
+
+    /// Evaluates the curve at each of the sorted parameters in 'ts' and writes the points to 'out'.
+    /// The curve is split at the parameter in the middle of the slice, which yields its point,
+    /// and the parameters on either side are evaluated recursively on the respective sub-curve
+    /// after remapping them to its parameter range. This requires no allocation and
+    /// the recursion depth is logarithmic in the number of parameters.
+    /// Panics if 'ts' and 'out' differ in length.
+    pub fn eval_batch<F>(&self, ts: &[F], out: &mut [P])
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        assert!(ts.len() == out.len());
+        debug_assert!(ts.windows(2).all(|w| w[0] <= w[1]));
+        self.eval_batch_recursive(ts, out, 0.0, 1.0);
+    }
+
+    /// Evaluates the parameters 'ts' (in the range [t0, t1] of the original curve) on self,
+    /// which is the sub-curve of the original curve over [t0, t1]
+    fn eval_batch_recursive<F>(&self, ts: &[F], out: &mut [P], t0: NativeFloat, t1: NativeFloat)
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        if ts.is_empty() {
+            return
+        }
+        let range = t1 - t0;
+        // sub-curve too short to be split any further (or the parameters are all the same)
+        if range < EPSILON {
+            for p in out.iter_mut() {
+                *p = self.start;
+            }
+            return
+        }
+        if ts.len() == 1 {
+            out[0] = self.eval_casteljau((ts[0].into() - t0) / range);
+            return
+        }
+        let mid = ts.len() / 2;
+        let t_mid = ts[mid].into();
+        let (left, right) = self.split((t_mid - t0) / range);
+        out[mid] = left.end;
+        let (out_left, out_right) = out.split_at_mut(mid);
+        left.eval_batch_recursive(&ts[..mid], out_left, t0, t_mid);
+        right.eval_batch_recursive(&ts[mid + 1..], &mut out_right[1..], t_mid, t1);
+    }
+
+
+    /// Returns an iterator over the points of a polyline approximating the curve (flattening),
+    /// such that the deviation between the polyline and the curve does not exceed 'tolerance'.
+    /// The curve is recursively split in half until each piece is linear within 'tolerance'.
+    /// The points are generated lazily without allocation, both endpoints are included.
+    /// The subdivision depth is capped at FLATTEN_MAX_DEPTH (at most 2^FLATTEN_MAX_DEPTH segments)
+    /// to guarantee termination for unreasonably small tolerances.
+    pub fn flattened<F>(&self, tolerance: F) -> impl Iterator<Item = P>
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        Flattened {
+            stack: [(*self, 0, 1.0); FLATTEN_MAX_DEPTH + 1],
+            len: 1,
+            tolerance: tolerance.into(),
+            emitted_start: false,
+        }.map(|(_, p)| p)
+    }
+
+    /// Like flattened(), but returns the parameter t of each point on the curve with it as (t, point),
+    /// e.g. to interpolate attributes along the polyline. The parameters are strictly increasing
+    /// from 0 to 1 and are exact, as the curve is only ever split in half.
+    #[cfg(feature = "alloc")]
+    pub fn flattened_with_t<F>(&self, tolerance: F) -> Vec<(F, P)>
+    where
+    F: Float
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>
+    {
+        Flattened {
+            stack: [(*self, 0, 1.0); FLATTEN_MAX_DEPTH + 1],
+            len: 1,
+            tolerance: tolerance.into(),
+            emitted_start: false,
+        }.map(|(t, p)| (t.into(), p))
+         .collect()
+    }
+
+    /// Returns the points of a polyline approximating the curve (flattening), such that the direction
+    /// of the curve turns by at most 'max_angle' (in radians) across each line segment.
+    /// Unlike flattened(), the number of points doesn't depend on the scale of the curve,
+    /// so strokes look equally smooth at any zoom level. Both endpoints are included.
+    /// The turning of a piece is bounded by the sum of the angles between the edges of its control polygon,
+    /// which is refined by splitting the curve in half. Pieces turning by less than FLATTEN_MIN_ANGLE
+    /// (i.e. straight ones up to rounding errors) are never split, while the depth is capped at FLATTEN_MAX_DEPTH
+    /// for cusps, which turn by π at any scale.
+    #[cfg(feature = "alloc")]
+    pub fn flattened_angular<F>(&self, max_angle: F) -> Vec<P>
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        let mut points = Vec::new();
+        points.push(self.start);
+        self.flatten_angular_recursive(max_angle.into(), 0, &mut points);
+        points
+    }
+
+    /// Recursive part of flattened_angular(), pushes the end points of the pieces
+    #[cfg(feature = "alloc")]
+    fn flatten_angular_recursive(&self, max_angle: NativeFloat, depth: usize, points: &mut Vec<P>) {
+        if depth >= FLATTEN_MAX_DEPTH || self.control_polygon_turning() <= max_angle.max(FLATTEN_MIN_ANGLE) {
+            points.push(self.end);
+            return;
+        }
+        let (left, right) = self.split(0.5);
+        left.flatten_angular_recursive(max_angle, depth + 1, points);
+        right.flatten_angular_recursive(max_angle, depth + 1, points);
+    }
+
+    /// Returns the sum of the (unsigned) angles between consecutive edges of the control polygon,
+    /// skipping edges of (nearly) zero length. This is an upper bound of the turning of the curve.
+    #[cfg(feature = "alloc")]
+    fn control_polygon_turning(&self) -> NativeFloat {
+        let edges = [self.ctrl1 - self.start, self.ctrl2 - self.ctrl1, self.end - self.ctrl2];
+        let mut turning = 0.0;
+        let mut prev: Option<P> = None;
+        for edge in edges.iter() {
+            let length = edge.length();
+            if length < EPSILON {
+                continue;
+            }
+            let edge = *edge * (1.0 / length);
+            if let Some(prev) = prev {
+                // atan2 of the sine and cosine is accurate for small angles, unlike acos of the cosine
+                let cos = prev.dot(&edge);
+                let sin = (edge - prev * cos).length();
+                turning += sin.atan2(cos);
+            }
+            prev = Some(edge);
+        }
+        turning
+    }
+
+
     pub fn split<F>(&self, t: F) -> (Self, Self)
     where
     F: Float,
@@ -165,6 +434,130 @@ P: Point<Scalar = NativeFloat>
         );
     }
 
+    /// Splits the curve at multiple parameters 'ts' at once, returning the ordered sub-curves.
+    /// The parameters don't need to be sorted, they are remapped to the remaining
+    /// part of the curve internally after each split.
+    /// Parameters outside of the open interval (0,1) and duplicates are ignored.
+    /// An empty slice yields a single copy of the whole curve.
+    /// Panics if more than 7 valid parameters (8 sub-curves) are given.
+    pub fn split_at_multiple<F>(&self, ts: &[F]) -> ArrayVec<[CubicBezier<P>; 8]>
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Mul<F, Output = F>
+        + Into<F>
+    {
+        let mut params: ArrayVec<[F; 7]> = ArrayVec::new();
+        for &t in ts {
+            if t > 0.0.into() && t < 1.0.into() && !params.contains(&t) {
+                params.push(t);
+            }
+        }
+        params.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut result = ArrayVec::new();
+        let mut remainder = *self;
+        let mut t_prev: F = 0.0.into();
+        for t in params {
+            // map t onto the parameter range [t_prev, 1] of the remaining curve
+            // (t_prev < 1 as all parameters are inside of (0,1))
+            let (left, right) = remainder.split((t - t_prev) / (1.0 - t_prev));
+            result.push(left);
+            remainder = right;
+            t_prev = t;
+        }
+        result.push(remainder);
+        result
+    }
+
+    /// Splits the curve into K pieces over equally long parameter intervals [i/K, (i+1)/K],
+    /// with a fixed size result e.g. for uploading the control points to the GPU.
+    /// Consecutive pieces share their end points exactly. K = 1 returns a copy of the curve, K = 0 an empty array.
+    pub fn subdivide<const K: usize>(&self) -> [CubicBezier<P>; K] {
+        let mut result = [CubicBezier::default(); K];
+        if K == 0 {
+            return result
+        }
+        let mut remainder = *self;
+        for (i, piece) in result.iter_mut().take(K-1).enumerate() {
+            // the remaining curve covers [i/K, 1], so the next piece ends at 1/(K-i) of it
+            let (left, right) = remainder.split(1.0 / (K - i) as NativeFloat);
+            *piece = left;
+            remainder = right;
+        }
+        result[K-1] = remainder;
+        result
+    }
+
+    /// Splits the curve into n pieces of equal arc length.
+    /// The parameters dividing the curve are found in a lookup table of the cumulative arc length
+    /// at ARCLEN_LUT_STEPS equally spaced parameters and refined with Newton iterations,
+    /// see t_at_arclen(). Panics if n is 0 or greater than 16.
+    pub fn split_equidistant(&self, n: usize) -> ArrayVec<[CubicBezier<P>; 16]> {
+        assert!(n > 0 && n <= 16);
+        let derivative = self.derivative::<NativeFloat>();
+        let lut = self.arclen_lut();
+        let total = lut[ARCLEN_LUT_STEPS];
+
+        let mut result = ArrayVec::new();
+        let mut remainder = *self;
+        let mut t_prev = 0.0;
+        for i in 1..n {
+            let t = Self::t_at_arclen(&derivative, &lut, total * i as NativeFloat / n as NativeFloat);
+            // map t onto the parameter range [t_prev, 1] of the remaining curve,
+            // which may have vanished due to rounding in the inversion of the arc length
+            let range = 1.0 - t_prev;
+            let local = if range < EPSILON { 1.0 } else { ((t - t_prev) / range).clamp(0.0, 1.0) };
+            let (left, right) = remainder.split(local);
+            result.push(left);
+            remainder = right;
+            t_prev = t;
+        }
+        result.push(remainder);
+        result
+    }
+
+    /// Returns the cumulative arc length of the curve at ARCLEN_LUT_STEPS+1 equally spaced parameters
+    fn arclen_lut(&self) -> [NativeFloat; ARCLEN_LUT_STEPS + 1] {
+        let derivative = self.derivative::<NativeFloat>();
+        let mut lut = [0.0; ARCLEN_LUT_STEPS + 1];
+        for i in 1..=ARCLEN_LUT_STEPS {
+            let t0 = (i - 1) as NativeFloat / ARCLEN_LUT_STEPS as NativeFloat;
+            let t1 = i as NativeFloat / ARCLEN_LUT_STEPS as NativeFloat;
+            lut[i] = lut[i - 1] + Self::gauss_legendre(&derivative, t0, t1);
+        }
+        lut
+    }
+
+    /// Returns the parameter t at which the arc length of the curve reaches 'arclen'.
+    /// The lookup table brackets t between two of its parameters, from there it is refined by Newton
+    /// iterations on the arc length, using the magnitude of the derivative as its derivative.
+    fn t_at_arclen(derivative: &QuadraticBezier<P>, lut: &[NativeFloat; ARCLEN_LUT_STEPS + 1], arclen: NativeFloat) -> NativeFloat {
+        let i = match lut.iter().position(|l| *l >= arclen) {
+            Some(0) => return 0.0,
+            Some(i) => i,
+            None => return 1.0,
+        };
+        let (t0, t1) = ((i - 1) as NativeFloat / ARCLEN_LUT_STEPS as NativeFloat,
+                        i as NativeFloat / ARCLEN_LUT_STEPS as NativeFloat);
+        let (l0, l1) = (lut[i - 1], lut[i]);
+        // linear interpolation within the bracket as initial guess
+        let mut t = if l1 - l0 < EPSILON { t0 } else { t0 + (t1 - t0) * (arclen - l0) / (l1 - l0) };
+        for _ in 0..ARCLEN_NEWTON_STEPS {
+            let speed = derivative.eval(t).length();
+            if speed < EPSILON {
+                break;
+            }
+            let error = l0 + Self::gauss_legendre(derivative, t0, t) - arclen;
+            t = (t - error / speed).max(t0).min(t1);
+        }
+        t
+    }
+
     /// Return the derivative function.
     /// The derivative is also a bezier curve but of degree n-1 (cubic->quadratic)
     /// Since it returns the derivative function, eval() needs to be called separately
@@ -188,6 +581,60 @@ P: Point<Scalar = NativeFloat>
 
 
 
+    /// Returns the equivalent curve of degree 4 with one more control point (degree elevation).
+    /// The new control points are obtained as
+    ///     new_i = i/(n+1) * old_(i-1) + (1 - i/(n+1)) * old_i
+    /// for i = 0..n+1 where n=3 is the degree of the original curve, so the shape remains unchanged.
+    pub fn elevate(&self) -> Bezier<P, 5> {
+        Bezier::new([
+            self.start,
+            self.start * 0.25 + self.ctrl1 * 0.75,
+            self.ctrl1 * 0.5 + self.ctrl2 * 0.5,
+            self.ctrl2 * 0.75 + self.end * 0.25,
+            self.end,
+        ])
+    }
+
+
+
+    /// Returns the best fitting quadratic curve (degree reduction) along with an error estimate,
+    /// which is the maximum distance between the two curves sampled at the same parameters.
+    /// The control point of the quadratic is obtained from the midpoint-of-cubic-controls formula
+    ///     ctrl = (3 * (ctrl1 + ctrl2) - (start + end)) / 4
+    /// which is exact if the cubic was obtained by elevating a quadratic.
+    /// Since this is generally an approximation, the returned error should be checked against
+    /// the desired tolerance. If it is exceeded, the curve should be split and each part reduced separately.
+    pub fn reduce<F>(&self) -> (QuadraticBezier<P>, F)
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let quadratic = QuadraticBezier::new(
+            self.start,
+            ((self.ctrl1 + self.ctrl2) * 3.0 - (self.start + self.end)) * 0.25,
+            self.end,
+        );
+        // sample the maximum deviation between the curves
+        let nsteps: usize = 100;
+        let mut max_err: NativeFloat = 0.0;
+        for t in 1..nsteps {
+            let t: F = (t as NativeFloat / nsteps as NativeFloat).into();
+            let err = (self.eval_casteljau(t) - quadratic.eval_casteljau(t)).squared_length();
+            if err > max_err {
+                max_err = err;
+            }
+        }
+        return (quadratic, max_err.sqrt().into())
+    }
+
+
     /// Direct Derivative - Sample the axis coordinate at 'axis' of the curve's derivative at t.
     /// Parameters: 
     /// t: the sampling parameter on the curve interval [0..1]
@@ -218,511 +665,3336 @@ P: Point<Scalar = NativeFloat>
     }
 
 
-
-
-    // pub fn curvature<F>(&self, t: F) -> F
-    // where
-    // F: Float,
-    // P:  Sub<P, Output = P>
-    //     + Add<P, Output = P>
-    //     + Mul<F, Output = P>,
-    // NativeFloat: Sub<F, Output = F> 
-    //     + Add<F, Output = F>
-    //     + Mul<F, Output = F>
-    //     + Float
-    //     + Into<F>
-    // {
-    //     let d = self.derivative();
-    //     let dd = d.derivative();
-    //     let dx = d.x(t);
-    //     let dy = d.y(t);
-    //     let ddx = dd.x(t);
-    //     let ddy = dd.y(t);
-    //     let numerator = dx * ddy.into() - ddx * dy;
-    //     let denominator = (dx*dx + dy*dy).powf(1.5.into());
-    //     return numerator / denominator
-    // }
-
-    // pub fn radius<F>(&self, t: F) -> F
-    // where
-    // F: Float,
-    // P:  Sub<P, Output = P>
-    //     + Add<P, Output = P>
-    //     + Mul<F, Output = P>,
-    // NativeFloat: Sub<F, Output = F> 
-    //     + Add<F, Output = F>
-    //     + Mul<F, Output = F>
-    //     + Float
-    //     + Into<F>
-    // {
-    //     return 1.0.into() / self.curvature(t)
-    // }
-
-
-    pub fn baseline(&self) -> LineSegment<P> {
-        LineSegment {
-            start: self.start,
-            end: self.end,
-        }
-    }
-
-    pub fn is_linear<F>(&self, tolerance: F) -> bool 
+    /// Returns the speed of the curve at t, i.e. the length of the derivative |B'(t)|,
+    /// which is the rate of change of the arc length with t. It vanishes at cusps.
+    pub fn speed<F>(&self, t: F) -> F
     where
-    F: Float 
-        + Default
-        + Into<NativeFloat>,
-    P: Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
-        + Into<F> 
+        + Into<F>
     {
-        // if start and end are (nearly) the same
-        if (self.start-self.end).squared_length() < EPSILON {
-            return false;
-        } 
-        // else check if ctrl points lie on baseline
-        self.are_points_colinear(tolerance)
+        return self.derivative::<F>().eval_casteljau(t).length().into()
     }
 
 
-    fn are_points_colinear<F>(&self, tolerance: F) -> bool
+    /// Returns the unit tangent vector of the curve at t.
+    /// If the derivative vanishes at t (e.g. at a cusp or where a control point coincides with an endpoint),
+    /// the direction of the second derivative is used instead.
+    /// If that vanishes as well, the curve is degenerate at t and a zero vector (P::default()) is returned.
+    pub fn tangent<F>(&self, t: F) -> P
     where
-    F: Float
-        + Default
-        + Into<NativeFloat>,
-    P: Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
         + Into<F>
     {
-        let lineeq = self.baseline();//.to_line().equation();
-        lineeq.distance_to_point(self.ctrl1) <= tolerance
-            && lineeq.distance_to_point(self.ctrl2) <= tolerance
+        let derivative = self.derivative::<F>();
+        let mut d = derivative.eval_casteljau(t);
+        // fall back to the next higher order derivative
+        if d.squared_length() < EPSILON {
+            d = derivative.derivative::<F>().eval(t);
+        }
+        if d.squared_length() < EPSILON {
+            return P::default()
+        }
+        return d.normalize()
     }
 
-    // Returs if the whole set of control points can be considered one singular point 
-    // given some tolerance. 
-    // TODO use machine epsilon vs squared_length OK?
-    pub(crate) fn is_a_point<F>(&self, tolerance: F) -> bool 
+
+    /// Returns the unit normal vector of the curve at t,
+    /// which is the tangent rotated counter-clockwise by 90°.
+    /// Normals are only well-defined for 2D curves, so only the first two axes are used.
+    pub fn normal<F>(&self, t: F) -> P
     where
     F: Float,
     P:  Sub<P, Output = P>
         + Add<P, Output = P>
         + Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
         + Into<F>
     {
-        let tolerance_squared = tolerance * tolerance;
-        // Use <= so that tolerance can be zero.
-        (self.start-self.end).squared_length().into() <= tolerance_squared
-            && (self.start-self.ctrl1).squared_length().into() <= tolerance_squared
-            && (self.end-self.ctrl2).squared_length().into() <= tolerance_squared
+        debug_assert!(P::DIM == 2);
+        let tangent = self.tangent(t);
+        let mut normal = P::default();
+        normal.set_axis(0, -tangent.axis(1));
+        normal.set_axis(1, tangent.axis(0));
+        return normal
     }
 
-    /// Compute the real roots of the cubic bezier function with
-    /// parameters of the form a*t^3 + b*t^2 + c*t + d for each dimension
-    /// using cardano's algorithm (code adapted from github.com/nical/lyon)
-    /// returns an ArrayVec of the present roots (max 3)
-    fn real_roots<F>(&self, a: F, b: F, c: F, d: F) -> ArrayVec<[F; 3]>
+
+    /// Returns the heading angle of the curve at t in radians in [-π, π],
+    /// which is the angle of the tangent to the x axis (atan2(dy, dx)).
+    /// Only the first two axes are used.
+    pub fn tangent_angle<F>(&self, t: F) -> F
     where
-    F:  Float
-        + Default,
+    F: Float,
     P:  Sub<P, Output = P>
         + Add<P, Output = P>
         + Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
         + Into<F>
     {
-        let mut result = ArrayVec::new();
-
-        let pi = 3.141592.into();
-
-        // check if can be handled below cubic order
-        if a.abs() < EPSILON.into() {
-            if b.abs() < EPSILON.into() {
-                if c.abs() < EPSILON.into() {
-                    // no solutions
-                    return result;
-                }
-                // is linear equation
-                result.push(-d / c);
-                return result;
-            }
-            // is quadratic equation
-            let delta = c * c - 4.0.into() * b * d;
-            if delta > 0.0.into() {
-                let sqrt_delta = delta.sqrt();
-                result.push((-c - sqrt_delta) / (2.0.into() * b));
-                result.push((-c + sqrt_delta) / (2.0.into() * b));
-            } else if delta.abs() < EPSILON.into() {
-                result.push(-c / (2.0.into() * b));
-            }
-            return result;
-        }
-
-        // is cubic equation -> use cardano's algorithm
-        let frac_1_3 = 1.0.into() / 3.0.into();
-
-        let bn = b / a;
-        let cn = c / a;
-        let dn = d / a;
-    
-        let delta0 = (3.0.into() * cn - bn * bn) / 9.0.into();
-        let delta1 = (9.0.into() * bn * cn - 27.0.into() * dn - 2.0.into() * bn * bn * bn) / 54.0.into();
-        let delta_01 = delta0 * delta0 * delta0 + delta1 * delta1;
-    
-        if delta_01 >= 0.0.into() {
-            let delta_p_sqrt = delta1 + delta_01.sqrt();
-            let delta_m_sqrt = delta1 - delta_01.sqrt();
-    
-            let s = delta_p_sqrt.signum() * delta_p_sqrt.abs().powf(frac_1_3);
-            let t = delta_m_sqrt.signum() * delta_m_sqrt.abs().powf(frac_1_3);
-    
-            result.push(-bn * frac_1_3 + (s + t));
-    
-            // Don't add the repeated root when s + t == 0.
-            if (s - t).abs() < EPSILON.into() && (s + t).abs() >= EPSILON.into() {
-                result.push(-bn * frac_1_3 - (s + t) / 2.0.into());
-            }
-        } else {
-            let theta = (delta1 / (-delta0 * delta0 * delta0).sqrt()).acos();
-            let two_sqrt_delta0 = 2.0.into() * (-delta0).sqrt();
-            result.push(two_sqrt_delta0 * Float::cos(theta * frac_1_3) - bn * frac_1_3);
-            result.push(
-                two_sqrt_delta0 * Float::cos((theta + 2.0.into() * pi) * frac_1_3) - bn * frac_1_3,
-            );
-            result.push(
-                two_sqrt_delta0 * Float::cos((theta + 4.0.into() * pi) * frac_1_3) - bn * frac_1_3,
-            );
-        }
-    
-        result
+        debug_assert!(P::DIM == 2);
+        let tangent = self.tangent(t);
+        let angle = tangent.axis(1).atan2(tangent.axis(0));
+        return angle.into()
     }
 
-    /// Solves the cubic bezier function given the control points' x OR y values
-    /// by solving the roots for x or y axis functions
-    /// Returns those roots of the function that are in the interval [0.0, 1.0].
-    fn solve_t_for_axis<F>(&self, value: F, axis: usize) -> ArrayVec<[F; 3]> 
+    /// Returns the total signed turning angle of the curve in radians by summing up the changes
+    /// of the tangent angle between nsteps+1 equally spaced parameters.
+    /// Each change is wrapped to [-π, π], so the angle accumulates continuously
+    /// across the discontinuity of atan2 at ±π. It is positive for counter-clockwise turns.
+    /// nsteps has to be large enough that the tangent turns less than π between two samples.
+    pub fn total_turning<F>(&self, nsteps: usize) -> F
     where
-    F:  Float
-        + Default
-        + Into<NativeFloat>,
+    F: Float,
     P:  Sub<P, Output = P>
         + Add<P, Output = P>
         + Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
         + Into<F>
     {
-        let mut result = ArrayVec::new();
-        // check if all points are the same or if the curve is really a line
-        if self.is_a_point(0.0.into())
-            || (self.are_points_colinear(0.0.into()) )// && self.start.y() == self.end.y())
-        {
-            return result
-        }
-        // these are just the x or y components of the points
-        let a = -self.start.axis(axis) + 
-                    3.0 * self.ctrl1.axis(axis).into()
-                    - 3.0 * self.ctrl2.axis(axis).into()
-                    + self.end.axis(axis).into();
-        let b = 3.0.into() * self.start.axis(axis).into()
-                    - 6.0.into() * self.ctrl1.axis(axis).into()
-                    + 3.0.into() * self.ctrl2.axis(axis).into();
-        let c = -3.0.into() * self.start.axis(axis).into() 
-                    + 3.0.into() * self.ctrl1.axis(axis).into();
-        let d = self.start.axis(axis) - value;
-
-        let roots = self.real_roots(a, b, c, d);
-        for root in roots {
-            if root > 0.0.into() && root < 1.0.into() {
-                result.push(root);
+        let pi = core::f64::consts::PI as NativeFloat;
+        let mut turning: NativeFloat = 0.0;
+        let mut prev = self.tangent_angle::<NativeFloat>(0.0);
+        for t in 1..=nsteps {
+            let t = t as NativeFloat * 1.0/(nsteps as NativeFloat);
+            let angle = self.tangent_angle::<NativeFloat>(t);
+            let mut delta = angle - prev;
+            if delta > pi {
+                delta -= 2.0 * pi;
+            } else if delta < -pi {
+                delta += 2.0 * pi;
             }
+            turning += delta;
+            prev = angle;
         }
-
-        result
+        return turning.into()
     }
 
-    /// Return the bounding box of the curve as an array of (min, max) tuples for each dimension (its index)
-    pub fn bounding_box<F>(&self) -> [(F, F); P::DIM] 
+
+
+
+    /// Calculates the signed curvature of the curve at point t
+    /// The curvature is the inverse of the radius of the tangential circle at t: k=1/r
+    /// It is positive where the curve turns counter-clockwise and negative where it turns clockwise.
+    /// Curvature as a scalar is only meaningful in the plane, so only the first two axes are used.
+    pub fn curvature<F>(&self, t: F) -> F
     where
-    F: Float
-        + Default,
+    F: Float,
     P:  Sub<P, Output = P>
         + Add<P, Output = P>
         + Mul<F, Output = P>,
-    NativeFloat: Sub<F, Output = F> 
+    NativeFloat: Sub<F, Output = F>
         + Add<F, Output = F>
         + Mul<F, Output = F>
         + Float
         + Into<F>
     {
-        // calculate coefficients for the derivative: at^2 + bt + c
-        // from the expansion of the cubic bezier curve: sum_i=0_to_3( binomial(3, i) * t^i * (1-t)^(n-i) )
-        // yields coeffcients
-        // po: [1, -2,  1]
-        // p1: [0,  2, -2]
-        // p2: [0,  0,  1]
-        //      c   b   a
-        let mut bounds = [(0.0.into(), 0.0.into()); P::DIM];
-        let derivative = self.derivative();
-        // calculate coefficients for derivative
-        let a: P = derivative.start + derivative.ctrl * -2.0.into() + derivative.end;
-        let b: P = derivative.start * -2.0.into() + derivative.ctrl * 2.0.into();
+        debug_assert!(P::DIM == 2);
+        let d = self.derivative::<F>();
+        let dd = d.derivative::<F>();
+        let dx = d.axis(t, 0);
+        let dy = d.axis(t, 1);
+        let ddx = dd.axis(t, 0);
+        let ddy = dd.axis(t, 1);
+        let numerator = dx * ddy - ddx * dy;
+        let denominator = (dx*dx + dy*dy).powf(1.5.into());
+        return numerator / denominator
+    }
+
+    /// Returns the parameter t in [0,1] where the magnitude of the curvature is largest, e.g. the apex of a bend.
+    /// The curvature is sampled at uniformly spaced parameters and the largest sample is refined
+    /// with a golden-section search between its neighbouring samples, which also converges
+    /// to an end point of the curve if the peak is located there.
+    /// At a cusp the curvature is infinite, so its parameter is returned.
+    /// The search assumes the peak is wider than the sample spacing, a narrower peak may be missed for a lower one.
+    pub fn max_curvature<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        // number of uniform samples and golden-section iterations (shrinking the interval to 0.618^steps)
+        let nsteps: usize = 64;
+        let max_iter: usize = 40;
+        let abs_curvature = |t: NativeFloat| self.curvature::<NativeFloat>(t).abs();
+
+        let mut i_max: usize = 0;
+        let mut k_max = abs_curvature(0.0);
+        for i in 1..=nsteps {
+            let k = abs_curvature(i as NativeFloat / nsteps as NativeFloat);
+            // NaN curvature (zero speed without a turn) is ignored
+            if k > k_max || k_max.is_nan() {
+                i_max = i;
+                k_max = k;
+            }
+        }
+        let mut a = i_max.saturating_sub(1) as NativeFloat / nsteps as NativeFloat;
+        let mut b = (i_max + 1).min(nsteps) as NativeFloat / nsteps as NativeFloat;
+        let inv_phi = (5.0 as NativeFloat).sqrt() * 0.5 - 0.5;
+        let mut c = b - (b - a) * inv_phi;
+        let mut d = a + (b - a) * inv_phi;
+        let (mut k_c, mut k_d) = (abs_curvature(c), abs_curvature(d));
+        for _ in 0..max_iter {
+            if k_c > k_d {
+                b = d;
+                d = c;
+                k_d = k_c;
+                c = b - (b - a) * inv_phi;
+                k_c = abs_curvature(c);
+            } else {
+                a = c;
+                c = d;
+                k_c = k_d;
+                d = a + (b - a) * inv_phi;
+                k_d = abs_curvature(d);
+            }
+        }
+        let t = (a + b) * 0.5;
+        // never return a worse parameter than the best sample
+        let k = abs_curvature(t);
+        if k.is_nan() || k < k_max {
+            return (i_max as NativeFloat / nsteps as NativeFloat).into()
+        }
+        return t.into()
+    }
+
+    /// Samples a curvature comb at nsteps+1 equally spaced parameters for visualization.
+    /// Returns pairs of (point on the curve, tip of the comb), where the tip is the point
+    /// offset along the normal by scale * curvature(t). As the curvature is signed, the comb
+    /// switches sides at inflections, where its length is zero.
+    #[cfg(feature = "alloc")]
+    pub fn curvature_comb<F>(&self, nsteps: usize, scale: F) -> Vec<(P, P)>
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        let scale = scale.into();
+        let mut comb = Vec::with_capacity(nsteps + 1);
+        for t in 0..=nsteps {
+            let t = t as NativeFloat * 1.0/(nsteps as NativeFloat);
+            let p = self.eval_casteljau(t);
+            let tip = p + self.normal(t) * (scale * self.curvature(t));
+            comb.push((p, tip));
+        }
+        comb
+    }
+
+    /// Calculates the radius of the tangential circle at t
+    /// It is the inverse of the curvature at t: r=1/k
+    pub fn radius<F>(&self, t: F) -> F
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        return 1.0.into() / self.curvature(t)
+    }
+
+
+    /// Returns the parameter t and the distance of the point on the curve closest to p as a tuple (t, distance).
+    /// Candidates are seeded by sampling the curve at uniformly spaced parameters.
+    /// The closest candidate is then refined with Newton iterations on the derivative
+    /// of the squared distance function, clamping t to [0,1] in each iteration.
+    /// The refinement stops early if a step would move away from p, so the result is never
+    /// worse than the best sampled candidate (e.g. near cusps).
+    pub fn project<F>(&self, p: P) -> (F, F)
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        // number of uniform samples to seed the refinement and max. number of newton iterations
+        let nsteps: usize = 64;
+        let max_iter: usize = 16;
+
+        let mut t_min: F = 0.0.into();
+        let mut dist_min: NativeFloat = (self.start - p).squared_length();
+        for i in 1..=nsteps {
+            let t: F = (i as NativeFloat / nsteps as NativeFloat).into();
+            let dist = (self.eval_casteljau(t) - p).squared_length();
+            if dist < dist_min {
+                dist_min = dist;
+                t_min = t;
+            }
+        }
+
+        // minimize the squared distance d(t) = |B(t) - p|^2 by finding the root of
+        //  d'(t)/2 = (B(t) - p) . B'(t)
+        // with derivative
+        // d''(t)/2 = B'(t) . B'(t) + (B(t) - p) . B''(t)
+        let derivative = self.derivative::<F>();
+        let second_derivative = derivative.derivative::<F>();
+        let mut t = t_min;
+        for _ in 0..max_iter {
+            let diff = self.eval_casteljau(t) - p;
+            let d1 = derivative.eval_casteljau(t);
+            let d2 = second_derivative.eval(t);
+            let f = diff.dot(&d1);
+            let df = d1.dot(&d1) + diff.dot(&d2);
+            if df.abs() < EPSILON {
+                break;
+            }
+            let t_next = (t - (f / df).into()).max(0.0.into()).min(1.0.into());
+            let dist = (self.eval_casteljau(t_next) - p).squared_length();
+            // bail out if the step diverges
+            if dist > dist_min {
+                break;
+            }
+            let converged = (t_next - t).abs() < EPSILON.into();
+            t = t_next;
+            dist_min = dist;
+            if converged {
+                break;
+            }
+        }
+        return (t, dist_min.sqrt().into())
+    }
+
+
+    /// Returns the parameters (t_self, t_other) of the closest points between the two curves
+    /// and their distance as a tuple (t_self, t_other, distance).
+    /// The end points of each curve are projected onto the other curve as initial candidates,
+    /// which are improved by alternately projecting the closest point of one curve onto the other.
+    /// The best candidate is then refined with Newton iterations on both parameters.
+    /// For intersecting curves, the distance is (close to) zero at any of the intersections.
+    pub fn min_distance<F>(&self, other: &CubicBezier<P>) -> (F, F, F)
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let max_iter: usize = 16;
+        let distance = |s: NativeFloat, t: NativeFloat| self.eval_casteljau(s).distance(&other.eval_casteljau(t));
+
+        let candidates = [(0.0, other.project::<NativeFloat>(self.start).0),
+                          (1.0, other.project::<NativeFloat>(self.end).0),
+                          (self.project::<NativeFloat>(other.start).0, 0.0),
+                          (self.project::<NativeFloat>(other.end).0, 1.0)];
+        let mut best = (0.0, 0.0, NativeFloat::infinity());
+        for &(s, t) in candidates.iter() {
+            // alternating projection, which never increases the distance
+            let (mut s, mut t, mut dist) = (s, t, distance(s, t));
+            for _ in 0..max_iter {
+                let t_next = other.project::<NativeFloat>(self.eval_casteljau(s)).0;
+                let (s_next, dist_next) = self.project::<NativeFloat>(other.eval_casteljau(t_next));
+                let converged = dist - dist_next < EPSILON;
+                if dist_next < dist {
+                    s = s_next;
+                    t = t_next;
+                    dist = dist_next;
+                }
+                if converged {
+                    break;
+                }
+            }
+            if dist < best.2 {
+                best = (s, t, dist);
+            }
+        }
+
+        // newton iterations minimizing d(s,t) = |A(s) - B(t)|^2 with the gradient (halved)
+        //  g = ((A - B) . A', -(A - B) . B')
+        // and hessian (halved)
+        //  H = | A'.A' + (A - B).A''   -A'.B'               |
+        //      | -A'.B'                B'.B' - (A - B).B''  |
+        let (d_self, d_other) = (self.derivative::<NativeFloat>(), other.derivative::<NativeFloat>());
+        let (dd_self, dd_other) = (d_self.derivative::<NativeFloat>(), d_other.derivative::<NativeFloat>());
+        let (mut s, mut t, mut dist) = best;
+        for _ in 0..max_iter {
+            let diff = self.eval_casteljau(s) - other.eval_casteljau(t);
+            let (a1, b1) = (d_self.eval_casteljau(s), d_other.eval_casteljau(t));
+            let (a2, b2) = (dd_self.eval(s), dd_other.eval(t));
+            let g = (diff.dot(&a1), -diff.dot(&b1));
+            let h11 = a1.dot(&a1) + diff.dot(&a2);
+            let h12 = -a1.dot(&b1);
+            let h22 = b1.dot(&b1) - diff.dot(&b2);
+            let determinant = h11 * h22 - h12 * h12;
+            if determinant.abs() < EPSILON {
+                break;
+            }
+            let s_next = (s - (h22 * g.0 - h12 * g.1) / determinant).clamp(0.0, 1.0);
+            let t_next = (t - (h11 * g.1 - h12 * g.0) / determinant).clamp(0.0, 1.0);
+            let dist_next = distance(s_next, t_next);
+            // bail out if the step diverges
+            if dist_next > dist {
+                break;
+            }
+            let converged = (s_next - s).abs() < EPSILON && (t_next - t).abs() < EPSILON;
+            s = s_next;
+            t = t_next;
+            dist = dist_next;
+            if converged {
+                break;
+            }
+        }
+        return (s.into(), t.into(), dist.into())
+    }
+
+
+    /// Returns the parameters t in (0,1) of the inflection points of a 2D curve,
+    /// where the curvature changes its sign.
+    /// With the derivative written in power basis B'(t)/3 = a + 2bt + ct^2 (and B''(t)/6 = b + ct),
+    /// the inflections are the roots of the cross product of first and second derivative:
+    ///     cross(b, c) * t^2 + cross(a, c) * t + cross(a, b) = 0
+    /// Only the first two axes of the points are used.
+    pub fn inflections<F>(&self) -> ArrayVec<[F; 2]>
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        debug_assert!(P::DIM == 2);
+        let a = self.ctrl1 - self.start;
+        let b = self.ctrl2 - self.ctrl1 - a;
+        let c = self.end - self.start + (self.ctrl1 - self.ctrl2) * 3.0;
+        let mut result = ArrayVec::new();
+        for root in quadratic_roots(b.cross_2d(&c), a.cross_2d(&c), a.cross_2d(&b)) {
+            if root > 0.0 && root < 1.0 {
+                result.push(root.into());
+            }
+        }
+        result
+    }
+
+
+    /// Returns the two distinct parameters (t1, t2) with t1 < t2 at which a 2D curve crosses itself,
+    /// or None if the curve has no loop.
+    /// With the curve in power basis B(t) = c0 + c1*t + c2*t^2 + c3*t^3, B(s) = B(t) for s != t
+    /// reduces to c3*(s^2 + s*t + t^2) + c2*(s + t) + c1 = 0. Writing u = s + t, v = s * t gives
+    ///     c3 * (u^2 - v) + c2 * u + c1 = 0
+    /// which is solved for u by eliminating c3 with the cross product and for v by projecting onto c3.
+    /// s and t are then the roots of x^2 - u*x + v, a loop only exists if both are real,
+    /// distinct and inside [0,1] (a double root is a cusp).
+    /// Only the first two axes of the points are used.
+    pub fn self_intersection<F>(&self) -> Option<(F, F)>
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        debug_assert!(P::DIM == 2);
+        let c1 = (self.ctrl1 - self.start) * 3.0;
+        let c2 = (self.ctrl2 - self.ctrl1 * 2.0 + self.start) * 3.0;
+        let c3 = self.end - self.ctrl2 * 3.0 + self.ctrl1 * 3.0 - self.start;
+
+        // the curve is (at most) a parabola which can't loop
+        let c3_squared = c3.dot(&c3);
+        let cross_c3_c2 = c3.cross_2d(&c2);
+        if c3_squared < EPSILON || cross_c3_c2.abs() < EPSILON {
+            return None;
+        }
+        let u = -c3.cross_2d(&c1) / cross_c3_c2;
+        let v = u * u + (c2.dot(&c3) * u + c1.dot(&c3)) / c3_squared;
+        let discriminant = u * u - 4.0 * v;
+        if discriminant <= EPSILON {
+            return None;
+        }
+        let sqrt_discriminant = discriminant.sqrt();
+        let t1 = (u - sqrt_discriminant) * 0.5;
+        let t2 = (u + sqrt_discriminant) * 0.5;
+        if t1 < 0.0 || t2 > 1.0 {
+            return None;
+        }
+        return Some((t1.into(), t2.into()))
+    }
+
+
+    /// Returns the parameter t in [0,1] of a cusp of the curve, where all components of the derivative
+    /// vanish simultaneously, or None if the curve is regular. The candidates are the roots of the derivative
+    /// of each axis and the end points (where a control point coinciding with its end point is a cusp),
+    /// of which the first one is returned at which the speed (see speed()) is negligible
+    /// relative to the size of the derivative's control points (CUSP_TOLERANCE).
+    pub fn cusp<F>(&self) -> Option<F>
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let derivative = self.derivative::<NativeFloat>();
+        let scale = derivative.start.length()
+                        .max(derivative.ctrl.length())
+                        .max(derivative.end.length());
+        if scale < EPSILON {
+            // the curve is a single point
+            return None;
+        }
+        let is_cusp = |t: NativeFloat| self.speed(t) <= CUSP_TOLERANCE * scale;
+        // axis_extrema() only returns roots inside of (0,1)
+        if is_cusp(0.0) {
+            return Some(0.0.into())
+        }
+        let mut cusp: Option<NativeFloat> = None;
+        for dim in 0..P::DIM {
+            for t in self.axis_extrema::<NativeFloat>(dim, 0.0) {
+                if is_cusp(t) && cusp.is_none_or(|c| t < c) {
+                    cusp = Some(t);
+                }
+            }
+        }
+        if cusp.is_none() && is_cusp(1.0) {
+            cusp = Some(1.0);
+        }
+        return cusp.map(|t| t.into())
+    }
+
+
+    /// Approximates the offset (parallel) curve at the signed distance along the normal
+    /// (positive distances offset to the left, see normal()) with a sequence of connected cubics.
+    /// The curve is first split at its inflection points. Each piece is offset by moving its end points
+    /// along their normals and scaling the control arms by the ratio of the offset and the original
+    /// radius of curvature (1 - distance * curvature), which is exact in the limit of circular arcs.
+    /// Pieces whose offset deviates more than tolerance from the sampled offset points
+    /// are subdivided further, so regions of high curvature are refined automatically.
+    /// If the 16 sub-curves don't suffice, the remaining pieces are offset without further refinement.
+    /// Only the first two axes of the points are used.
+    pub fn offset<F>(&self, distance: F, tolerance: F) -> ArrayVec<[CubicBezier<P>; 16]>
+    where
+    F: Float
+        + Into<NativeFloat>,
+    {
+        debug_assert!(P::DIM == 2);
+        let distance: NativeFloat = distance.into();
+        let tolerance: NativeFloat = tolerance.into();
+
+        let mut result: ArrayVec<[CubicBezier<P>; 16]> = ArrayVec::new();
+        // depth-first subdivision which processes the pieces in order
+        let mut stack: ArrayVec<[(CubicBezier<P>, usize); 16]> = ArrayVec::new();
+        let pieces = self.split_at_multiple(&self.inflections::<NativeFloat>());
+        for piece in pieces.iter().rev() {
+            stack.push((*piece, 0));
+        }
+        while let Some((piece, depth)) = stack.pop() {
+            let candidate = piece.offset_piece(distance);
+            if depth >= OFFSET_MAX_DEPTH
+                || result.len() + stack.len() + 2 > result.capacity()
+                || piece.offset_error(&candidate, distance) <= tolerance
+            {
+                result.push(candidate);
+                continue;
+            }
+            let (left, right) = piece.split(0.5);
+            stack.push((right, depth + 1));
+            stack.push((left, depth + 1));
+        }
+        result
+    }
+
+    /// Offsets a single piece without inflections by distance, see offset()
+    fn offset_piece(&self, distance: NativeFloat) -> Self {
+        let start = self.start + self.normal(0.0) * distance;
+        let end = self.end + self.normal(1.0) * distance;
+        // the control arms shrink towards the center of curvature and grow away from it
+        let scale = |t: NativeFloat| -> NativeFloat {
+            let factor = 1.0 - distance * self.curvature(t);
+            if factor.is_finite() { factor.max(0.0) } else { 1.0 }
+        };
+        CubicBezier::new(start,
+                        start + (self.ctrl1 - self.start) * scale(0.0),
+                        end + (self.ctrl2 - self.end) * scale(1.0),
+                        end)
+    }
+
+    /// Returns the maximum distance between the offset candidate and the points
+    /// at distance along the normal of self, sampled at the same parameters
+    fn offset_error(&self, candidate: &Self, distance: NativeFloat) -> NativeFloat {
+        let nsteps: usize = 8;
+        let mut max_err: NativeFloat = 0.0;
+        for t in 1..nsteps {
+            let t = t as NativeFloat * 1.0/(nsteps as NativeFloat);
+            let expected = self.eval(t) + self.normal(t) * distance;
+            max_err = max_err.max(candidate.eval(t).distance(&expected));
+        }
+        max_err
+    }
+
+
+    /// Returns the pairs of parameters (t_self, t_other) at which the two curves intersect.
+    /// The intersections are found by recursively subdividing both curves and discarding pairs
+    /// of sub-curves whose control point boxes do not overlap (the curves are contained in the
+    /// convex hull of their control points). Once the boxes of both sub-curves are small enough,
+    /// the points at the returned parameters are less than tolerance apart.
+    /// Intersections closer than tolerance to an already found one are ignored
+    /// and at most 9 intersections are returned (the maximum for two cubics which do not overlap).
+    pub fn intersections<F>(&self, other: &CubicBezier<P>, tolerance: F) -> ArrayVec<[(F, F); 9]>
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    NativeFloat: Into<F>
+    {
+        let mut found = ArrayVec::new();
+        self.intersect_recursive(other, (0.0, 1.0), (0.0, 1.0), tolerance.into(), 0, &mut found);
+        let mut result = ArrayVec::new();
+        for (t_self, t_other, _) in found {
+            result.push((t_self.into(), t_other.into()));
+        }
+        result
+    }
+
+    /// Recursive part of intersections(), the ranges are the parameter intervals
+    /// of self and other on the original curves.
+    /// Found intersections are stored with the point on self to filter duplicates.
+    fn intersect_recursive(&self,
+                            other: &CubicBezier<P>,
+                            range_self: (NativeFloat, NativeFloat),
+                            range_other: (NativeFloat, NativeFloat),
+                            tolerance: NativeFloat,
+                            depth: usize,
+                            found: &mut ArrayVec<[(NativeFloat, NativeFloat, P); 9]>)
+    {
+        if found.len() == found.capacity() {
+            return;
+        }
+        // squared diagonals of the boxes
+        let mut diag_self = 0.0;
+        let mut diag_other = 0.0;
+        for axis in 0..P::DIM {
+            let (min_self, max_self) = self.axis_bounds(axis);
+            let (min_other, max_other) = other.axis_bounds(axis);
+            if min_self > max_other || min_other > max_self {
+                return;
+            }
+            diag_self += (max_self - min_self) * (max_self - min_self);
+            diag_other += (max_other - min_other) * (max_other - min_other);
+        }
+
+        let mid_self = (range_self.0 + range_self.1) * 0.5;
+        let mid_other = (range_other.0 + range_other.1) * 0.5;
+        // both boxes overlap and each diagonal is smaller than tolerance/2,
+        // so any two points within them are less than tolerance apart
+        let max_diag = tolerance * tolerance * 0.25;
+        if depth >= INTERSECTION_MAX_DEPTH || (diag_self < max_diag && diag_other < max_diag) {
+            let point = self.eval(0.5);
+            if found.iter().all(|(_, _, p)| p.distance(&point) >= tolerance) {
+                found.push((mid_self, mid_other, point));
+            }
+            return;
+        }
+
+        let (self_left, self_right) = self.split(0.5);
+        let (other_left, other_right) = other.split(0.5);
+        for (sub_self, sub_range_self) in [(self_left, (range_self.0, mid_self)),
+                                           (self_right, (mid_self, range_self.1))].iter() {
+            for (sub_other, sub_range_other) in [(other_left, (range_other.0, mid_other)),
+                                                 (other_right, (mid_other, range_other.1))].iter() {
+                sub_self.intersect_recursive(sub_other, *sub_range_self, *sub_range_other,
+                                             tolerance, depth + 1, found);
+            }
+        }
+    }
+
+    /// Returns the minimum and maximum coordinate of the control points along axis
+    fn axis_bounds(&self, axis: usize) -> (NativeFloat, NativeFloat) {
+        let coords = [self.start.axis(axis), self.ctrl1.axis(axis),
+                      self.ctrl2.axis(axis), self.end.axis(axis)];
+        let mut bounds = (coords[0], coords[0]);
+        for c in coords.iter() {
+            bounds = (bounds.0.min(*c), bounds.1.max(*c));
+        }
+        bounds
+    }
+
+
+    /// Returns the sorted parameters t at which the ray from origin in direction dir crosses the curve.
+    /// The control points are transformed into the coordinate frame of the ray, where the
+    /// curve crosses the ray where its perpendicular coordinate is zero (see solve_t_for_axis()).
+    /// Only crossings in front of the origin (at a positive distance along the ray)
+    /// and in the open interval (0,1) are returned. The ray is 2D, so only the first two axes are used.
+    /// Returns no crossings if dir has zero length.
+    pub fn ray_intersections<F>(&self, origin: P, dir: P) -> ArrayVec<[F; 3]>
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        debug_assert!(P::DIM == 2);
+        let mut result = ArrayVec::new();
+        if dir.squared_length() < EPSILON {
+            return result
+        }
+        let dir = dir.normalize();
+        // axis 0 is the distance along the ray, axis 1 the signed distance to the ray
+        let to_ray_frame = |p: P| {
+            let v = p - origin;
+            let mut q = P::default();
+            q.set_axis(0, v.dot(&dir));
+            q.set_axis(1, dir.cross_2d(&v));
+            q
+        };
+        let local = CubicBezier::new(to_ray_frame(self.start), to_ray_frame(self.ctrl1),
+                                     to_ray_frame(self.ctrl2), to_ray_frame(self.end));
+        for t in local.solve_t_for_axis::<F>(0.0.into(), 1) {
+            if local.eval_casteljau(t).axis(0) > 0.0 {
+                result.push(t);
+            }
+        }
+        result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+
+    /// Returns the parameter intervals (t_in, t_out) in which the curve lies inside the axis-aligned
+    /// rectangle given by its min and max corner, in increasing order.
+    /// The curve is split at its crossings with the four lines through the sides of the rectangle
+    /// (see solve_t_for_value()), and the pieces whose midpoint lies inside are merged into intervals.
+    /// Pieces which only touch the rectangle in a single point are ignored.
+    /// A cubic can't enter the rectangle more than 5 times: its coordinates have at most two extrema,
+    /// so the curve enters the band between the lines of each axis at most 3 times.
+    /// Only the first two axes are used.
+    pub fn clip_to_rect<F>(&self, min: P, max: P) -> ArrayVec<[(F, F); 5]>
+    where
+    F:  Float
+        + Default
+        + Into<NativeFloat>,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        debug_assert!(P::DIM == 2);
+        let mut ts: ArrayVec<[NativeFloat; 14]> = ArrayVec::new();
+        ts.push(0.0);
+        ts.push(1.0);
+        for axis in 0..2 {
+            for value in [min.axis(axis), max.axis(axis)].iter() {
+                ts.extend(self.solve_t_for_value::<NativeFloat>(*value, axis));
+            }
+        }
+        ts.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut result: ArrayVec<[(F, F); 5]> = ArrayVec::new();
+        for pair in ts.windows(2) {
+            let (t0, t1) = (pair[0], pair[1]);
+            if t1 - t0 < EPSILON {
+                continue;
+            }
+            let mid = self.eval_casteljau((t0 + t1) * 0.5);
+            let inside = (0..2).all(|axis| mid.axis(axis) >= min.axis(axis)
+                                           && mid.axis(axis) <= max.axis(axis));
+            if !inside {
+                continue;
+            }
+            // extend the previous interval if this piece continues it
+            match result.last_mut() {
+                Some(last) if last.1 == t0.into() => last.1 = t1.into(),
+                _ => result.push((t0.into(), t1.into())),
+            }
+        }
+        result
+    }
+
+
+    pub fn baseline(&self) -> LineSegment<P> {
+        LineSegment {
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn is_linear<F>(&self, tolerance: F) -> bool 
+    where
+    F: Float 
+        + Default
+        + Into<NativeFloat>,
+    P: Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F> 
+    {
+        // if start and end are (nearly) the same
+        if (self.start-self.end).squared_length() < EPSILON {
+            return false;
+        } 
+        // else check if ctrl points lie on baseline
+        self.are_points_colinear(tolerance)
+    }
+
+
+    /// Returns the squared flatness metric of the curve: with the deviations of the control points
+    /// from their positions on a straight, uniformly parameterized line between start and end
+    ///     u = 3 * ctrl1 - 2 * start - end,  v = 3 * ctrl2 - start - 2 * end
+    /// it is the sum of max(u_i^2, v_i^2) over all axes i. The curve deviates at most
+    /// sqrt(flatness) / 4 from its chord, so it is flat enough for a tolerance
+    /// if flatness <= 16 * tolerance^2, which needs no square root.
+    /// Splitting the curve in half reduces the deviations to about a quarter, so the metric
+    /// shrinks by a factor of about 16 with every level of subdivision. A curve with flatness f
+    /// thus needs about log_16(f / (16 * tolerance^2)) levels to be flattened.
+    pub fn flatness<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let u = self.ctrl1 * 3.0 - self.start * 2.0 - self.end;
+        let v = self.ctrl2 * 3.0 - self.start - self.end * 2.0;
+        let mut flatness = 0.0;
+        for dim in 0..P::DIM {
+            flatness += (u.axis(dim) * u.axis(dim)).max(v.axis(dim) * v.axis(dim));
+        }
+        return flatness.into()
+    }
+
+
+    fn are_points_colinear<F>(&self, tolerance: F) -> bool
+    where
+    F: Float
+        + Default
+        + Into<NativeFloat>,
+    P: Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let lineeq = self.baseline();//.to_line().equation();
+        lineeq.distance_to_point(self.ctrl1) <= tolerance
+            && lineeq.distance_to_point(self.ctrl2) <= tolerance
+    }
+
+    // Returs if the whole set of control points can be considered one singular point 
+    // given some tolerance. 
+    // TODO use machine epsilon vs squared_length OK?
+    pub(crate) fn is_a_point<F>(&self, tolerance: F) -> bool 
+    where
+    F: Float,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let tolerance_squared = tolerance * tolerance;
+        // Use <= so that tolerance can be zero.
+        (self.start-self.end).squared_length().into() <= tolerance_squared
+            && (self.start-self.ctrl1).squared_length().into() <= tolerance_squared
+            && (self.end-self.ctrl2).squared_length().into() <= tolerance_squared
+    }
+
+    /// Returns the sorted parameters t in the open interval (0,1) at which the coordinate
+    /// of the curve along axis equals value, e.g. where it crosses a vertical line for axis 0.
+    /// Crossings exactly at the end points are not returned.
+    /// Returns no parameters if all points of the curve coincide.
+    pub fn solve_t_for_value<F>(&self, value: F, axis: usize) -> ArrayVec<[F; 3]>
+    where
+    F:  Float
+        + Default
+        + Into<NativeFloat>,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        self.solve_t_for_axis(value, axis)
+    }
+
+    /// Solves the cubic bezier function given the control points' x OR y values
+    /// by solving the roots for x or y axis functions
+    /// Returns those roots of the function that are in the open interval (0.0, 1.0).
+    fn solve_t_for_axis<F>(&self, value: F, axis: usize) -> ArrayVec<[F; 3]> 
+    where
+    F:  Float
+        + Default
+        + Into<NativeFloat>,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let mut result = ArrayVec::new();
+        // check if all points are the same
+        // (straight curves are solved like any other, cubic_roots() handles the lower degrees)
+        if self.is_a_point(0.0.into()) {
+            return result
+        }
+        // these are just the x or y components of the points
+        let a = -self.start.axis(axis)
+                    + 3.0 * self.ctrl1.axis(axis)
+                    - 3.0 * self.ctrl2.axis(axis)
+                    + self.end.axis(axis);
+        let b = 3.0 * self.start.axis(axis)
+                    - 6.0 * self.ctrl1.axis(axis)
+                    + 3.0 * self.ctrl2.axis(axis);
+        let c = -3.0 * self.start.axis(axis)
+                    + 3.0 * self.ctrl1.axis(axis);
+        let d = self.start.axis(axis) - value.into();
+
+        for root in cubic_roots(a, b, c, d) {
+            if root > 0.0 && root < 1.0 {
+                result.push(root.into());
+            }
+        }
+
+        result
+    }
+
+    /// Return the bounding box of the curve as an array of (min, max) tuples for each dimension (its index)
+    pub fn bounding_box<F>(&self) -> [(F, F); P::DIM] 
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let mut bounds = [(0.0.into(), 0.0.into()); P::DIM];
+
+        // calculate roots for t over x axis and plug them into the bezier function
+        //  to get x,y values (make vec 2 bigger for t=0,t=1 values)
+        // loop over any of the points dimensions (they're all the same)
+        for dim in 0..P::DIM {
+            let mut extrema: ArrayVec<[F; 4]> = ArrayVec::new();
+            // roots numerically just outside of [0,1] may still be extrema inside
+            extrema.extend(self.axis_extrema::<F>(dim, BBOX_ROOT_TOLERANCE));
+            // evaluates roots in original function
+            for t in extrema.iter_mut() {
+                *t = self.eval_casteljau(*t).axis(dim).into();
+            }
+            // add y-values for start and end point as candidates
+            extrema.push(self.start.axis(dim).into()); 
+            extrema.push(self.end.axis(dim).into());
+            // sort to get min and max values for bounding box
+            extrema.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+
+            // determine xmin, xmax, ymin, ymax, from the set {B(xroots), B(yroots), B(0), B(1)} 
+            // (Intermediate control points can't form a boundary)
+            // .unwrap() is ok as it can never be empty as it always at least contains the endpoints
+            bounds[dim] = (extrema[0], *extrema.last().unwrap());
+        }
+        return bounds
+    }
+
+    /// Return the bounding box of the control points as an array of (min, max) tuples for each dimension.
+    /// It always contains the curve (convex hull property) and needs no root finding,
+    /// but is generally larger than the tight bounding_box().
+    pub fn control_box<F>(&self) -> [(F, F); P::DIM]
+    where
+    F: Float,
+    NativeFloat: Into<F>
+    {
+        let mut bounds = [(0.0.into(), 0.0.into()); P::DIM];
+        for dim in 0..P::DIM {
+            let (min, max) = self.axis_bounds(dim);
+            bounds[dim] = (min.into(), max.into());
+        }
+        return bounds
+    }
+
+
+    /// Returns the sorted parameters t in (0,1) at which the derivative of any axis vanishes,
+    /// i.e. where a coordinate reaches a local minimum or maximum.
+    /// Parameters closer than machine epsilon to each other are only returned once.
+    /// There are at most two extrema per axis and the result has a fixed capacity of six,
+    /// so only the first three axes are considered, any further axes of the points are ignored.
+    pub fn extrema<F>(&self) -> ArrayVec<[F; 6]>
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        let mut roots: ArrayVec<[F; 6]> = ArrayVec::new();
+        for dim in 0..P::DIM.min(3) {
+            roots.extend(self.axis_extrema::<F>(dim, 0.0));
+        }
+        roots.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut result: ArrayVec<[F; 6]> = ArrayVec::new();
+        for t in roots {
+            if result.last().is_none_or(|last| (t - *last).abs() >= EPSILON.into()) {
+                result.push(t);
+            }
+        }
+        result
+    }
+
+
+    /// Splits the curve at its extrema, so each of the returned sub-curves
+    /// is monotone (non-decreasing or non-increasing) along every axis.
+    /// Like extrema(), only the first three axes are considered, so the sub-curves
+    /// may not be monotone along any further axes of the points.
+    pub fn into_monotone<F>(&self) -> ArrayVec<[CubicBezier<P>; 8]>
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        self.split_at_multiple(&self.extrema::<F>())
+    }
+
+
+    /// Returns the y coordinate of a 2D curve at the given x coordinate,
+    /// e.g. for CSS-style easing functions which are monotone in x.
+    /// If the curve crosses x more than once, the y coordinate at the first crossing is returned.
+    /// Returns None if x is outside the range of the end points' x coordinates
+    /// or if the curve doesn't reach x.
+    pub fn y_for_x<F>(&self, x: F) -> Option<F>
+    where
+    F:  Float
+        + Default
+        + Into<NativeFloat>,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        debug_assert!(P::DIM == 2);
+        let x: NativeFloat = x.into();
+        let (x0, x1) = (self.start.axis(0), self.end.axis(0));
+        if x < x0.min(x1) || x > x0.max(x1) {
+            return None
+        }
+        // solve_t_for_value() doesn't return the end points
+        if x == x0 {
+            return Some(self.start.axis(1).into())
+        }
+        if x == x1 {
+            return Some(self.end.axis(1).into())
+        }
+        let t = *self.solve_t_for_value::<NativeFloat>(x, 0).first()?;
+        return Some(self.eval_casteljau(t).axis(1).into())
+    }
+
+
+    /// Returns the parameters t in (0,1) at which the derivative of the given axis vanishes.
+    /// Roots up to 'tolerance' outside of the interval are not dropped but refined with newton
+    /// iterations on the bernstein form of the derivative, clamped to [0,1]. The power form loses
+    /// the precision near the end points, so such a root may belong to an extremum just inside.
+    fn axis_extrema<F>(&self, dim: usize, tolerance: NativeFloat) -> ArrayVec<[F; 2]>
+    where
+    F: Float
+        + Default,
+    P:  Sub<P, Output = P>
+        + Add<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F> 
+        + Add<F, Output = F>
+        + Mul<F, Output = F>
+        + Float
+        + Into<F>
+    {
+        // calculate coefficients for the derivative: at^2 + bt + c
+        // from the expansion of the cubic bezier curve: sum_i=0_to_3( binomial(3, i) * t^i * (1-t)^(n-i) )
+        // yields coeffcients
+        // po: [1, -2,  1]
+        // p1: [0,  2, -2]
+        // p2: [0,  0,  1]
+        //      c   b   a
+        let derivative = self.derivative();
+        let a: P = derivative.start + derivative.ctrl * -2.0.into() + derivative.end;
+        let b: P = derivative.start * -2.0.into() + derivative.ctrl * 2.0.into();
         let c: P = derivative.start;
 
-        // calculate roots for t over x axis and plug them into the bezier function
-        //  to get x,y values (make vec 2 bigger for t=0,t=1 values)
-        // loop over any of the points dimensions (they're all the same)
-        for (dim, _) in a.into_iter().enumerate() {
-            let mut extrema: ArrayVec<[F; 4]> = ArrayVec::new();
-            extrema.extend(derivative.real_roots(
-                                                a.axis(dim).into(), 
-                                                b.axis(dim).into(), 
-                                                c.axis(dim).into(), 
-                                                ).into_iter());
-            // only retain roots for which t is in [0..1] 
-            extrema.retain(|root| -> bool {root > &mut 0.0.into() && root < &mut 1.0.into()});
-            // evaluates roots in original function
-            for t in extrema.iter_mut() {
-                *t = self.eval_casteljau(*t).axis(dim).into();
+        let mut roots = ArrayVec::new();
+        // only retain roots for which t is in (0..1)
+        for root in quadratic_roots(a.axis(dim), b.axis(dim), c.axis(dim)) {
+            if root > 0.0 && root < 1.0 {
+                roots.push(root.into());
+            } else if root > -tolerance && root < 1.0 + tolerance {
+                let (d0, d1, d2) = (derivative.start.axis(dim), derivative.ctrl.axis(dim), derivative.end.axis(dim));
+                let mut t = root.clamp(0.0, 1.0);
+                for _ in 0..BBOX_NEWTON_STEPS {
+                    let slope = 2.0 * ((d1 - d0) * (1.0 - t) + (d2 - d1) * t);
+                    if slope == 0.0 {
+                        break;
+                    }
+                    t = (t - derivative.eval_casteljau(t).axis(dim) / slope).clamp(0.0, 1.0);
+                }
+                roots.push(t.into());
+            }
+        }
+        roots
+    }
+
+}
+
+
+// PointN<f64, N> only has the native scalar type without the 'f32' feature
+#[cfg(not(feature = "f32"))]
+impl<const N: usize> CubicBezier<PointN<f64, N>>
+where
+PointN<f64, N>: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<PointN<f64, N>, Output = PointN<f64, N>>
+    + Sub<PointN<f64, N>, Output = PointN<f64, N>>
+    + Mul<NativeFloat, Output = PointN<f64, N>>,
+{
+    /// Approximates the arc length of the curve by flattening it with nsteps straight line segments.
+    /// This is the recommended entry point for curves over PointN<f64, N>, as it computes the same
+    /// value as arclen() but doesn't require spelling out the generic float type and its bounds.
+    pub fn arclen_f64(&self, nsteps: usize) -> f64 {
+        self.arclen::<f64>(nsteps)
+    }
+}
+
+
+impl CubicBezier<PointN<f32, 2>>
+{
+    /// Evaluates the 2D curve at each of the parameters in 'ts' and writes the coordinates of the points
+    /// to 'out_x' and 'out_y' (structure of arrays). The loop body is the branch-free bernstein form of eval()
+    /// on plain f32 lanes, which the compiler can auto-vectorize to process several parameters at once.
+    /// With the 'f32' feature, the results are identical to eval(). The parameters need not be sorted.
+    /// Panics if the slices differ in length.
+    pub fn eval_batch_f32(&self, ts: &[f32], out_x: &mut [f32], out_y: &mut [f32]) {
+        assert!(ts.len() == out_x.len() && ts.len() == out_y.len());
+        let [x0, y0]: [f32; 2] = self.start.into();
+        let [x1, y1]: [f32; 2] = self.ctrl1.into();
+        let [x2, y2]: [f32; 2] = self.ctrl2.into();
+        let [x3, y3]: [f32; 2] = self.end.into();
+        for ((t, x), y) in ts.iter().zip(out_x.iter_mut()).zip(out_y.iter_mut()) {
+            let t = *t;
+            let b0 = (1.0 - t) * (1.0 - t) * (1.0 - t);
+            let b1 = 3.0 * t * (1.0 - t) * (1.0 - t);
+            let b2 = 3.0 * t * t * (1.0 - t);
+            let b3 = t * t * t;
+            *x = x0 * b0 + x1 * b1 + x2 * b2 + x3 * b3;
+            *y = y0 * b0 + y1 * b1 + y2 * b2 + y3 * b3;
+        }
+    }
+}
+
+
+/// Formats the curve as the list of its points "[start, ctrl1, ctrl2, end]",
+/// format options are passed on to the points.
+impl<P> fmt::Display for CubicBezier<P>
+where
+P: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        self.start.fmt(f)?;
+        f.write_str(", ")?;
+        self.ctrl1.fmt(f)?;
+        f.write_str(", ")?;
+        self.ctrl2.fmt(f)?;
+        f.write_str(", ")?;
+        self.end.fmt(f)?;
+        f.write_str("]")
+    }
+}
+
+impl<P> From<CubicBezier<P>> for Bezier<P, 4>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn from(cubic: CubicBezier<P>) -> Self {
+        Bezier::new([cubic.start, cubic.ctrl1, cubic.ctrl2, cubic.end])
+    }
+}
+
+impl<P> From<Bezier<P, 4>> for CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn from(bezier: Bezier<P, 4>) -> Self {
+        let [start, ctrl1, ctrl2, end] = bezier.control_points;
+        CubicBezier::new(start, ctrl1, ctrl2, end)
+    }
+}
+
+
+/// Exact conversion of a quadratic curve to a cubic by degree elevation
+impl<P> From<QuadraticBezier<P>> for CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn from(quadratic: QuadraticBezier<P>) -> Self {
+        CubicBezier::new(
+            quadratic.start,
+            quadratic.start + (quadratic.ctrl - quadratic.start) * (2.0 / 3.0),
+            quadratic.end + (quadratic.ctrl - quadratic.end) * (2.0 / 3.0),
+            quadratic.end,
+        )
+    }
+}
+
+
+/// A cubic curve in power (monomial) basis P(t) = a*t³ + b*t² + c*t + d, see CubicBezier::precompute().
+/// Evaluation with horner's scheme needs only three multiply-adds per axis, which pays off when
+/// the same curve is evaluated very often. Like CubicBezier::eval() it is less accurate than
+/// the De Casteljau algorithm, as the coefficients may be much larger than the control points.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct CubicPoly<P>
+{
+    pub (crate) a: P,
+    pub (crate) b: P,
+    pub (crate) c: P,
+    pub (crate) d: P,
+}
+
+impl<P> CubicPoly<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    /// Returns the coefficients (a, b, c, d) in order of decreasing degree
+    pub fn coefficients(&self) -> (P, P, P, P) {
+        (self.a, self.b, self.c, self.d)
+    }
+
+    /// Evaluate the curve at t as ((a*t + b)*t + c)*t + d
+    pub fn eval<F>(&self, t: F) -> P
+    where
+    F: Float,
+    P: Add<P, Output = P>
+        + Mul<F, Output = P>,
+    {
+        return ((self.a * t + self.b) * t + self.c) * t + self.d
+    }
+}
+
+
+/// A cubic Bezier curve which borrows its four points instead of owning copies of them,
+/// e.g. to evaluate curves that share points stored elsewhere without copying them into a CubicBezier first.
+/// Use to_owned() to get a CubicBezier with all methods.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CubicBezierRef<'a, P>
+{
+    pub (crate) start:  &'a P,
+    pub (crate) ctrl1:  &'a P,
+    pub (crate) ctrl2:  &'a P,
+    pub (crate) end:    &'a P,
+}
+
+impl<'a, P> CubicBezierRef<'a, P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    pub fn new(start: &'a P, ctrl1: &'a P, ctrl2: &'a P, end: &'a P) -> Self {
+        CubicBezierRef {
+            start,
+            ctrl1,
+            ctrl2,
+            end
+        }
+    }
+
+    /// Evaluate the curve at t using the numerically stable De Casteljau algorithm,
+    /// see CubicBezier::eval_casteljau()
+    pub fn eval<F>(&self, t: F) -> P
+    where
+    F: Float,
+    P: Add<P, Output = P>
+        + Sub<P, Output = P>
+        + Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Mul<F, Output = F>
+    {
+        let ctrl_1ab = *self.start + (*self.ctrl1 - *self.start) * t;
+        let ctrl_1bc = *self.ctrl1 + (*self.ctrl2 - *self.ctrl1) * t;
+        let ctrl_1cd = *self.ctrl2 + (*self.end - *self.ctrl2)   * t;
+        let ctrl_2ab  = ctrl_1ab + (ctrl_1bc - ctrl_1ab) * t;
+        let ctrl_2bc  = ctrl_1bc + (ctrl_1cd - ctrl_1bc) * t;
+        return ctrl_2ab + (ctrl_2bc - ctrl_2ab) * t
+    }
+
+    /// Returns a CubicBezier with copies of the borrowed points
+    pub fn to_owned(&self) -> CubicBezier<P> {
+        CubicBezier::new(*self.start, *self.ctrl1, *self.ctrl2, *self.end)
+    }
+}
+
+impl<'a, P> From<&'a CubicBezier<P>> for CubicBezierRef<'a, P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn from(cubic: &'a CubicBezier<P>) -> Self {
+        CubicBezierRef::new(&cubic.start, &cubic.ctrl1, &cubic.ctrl2, &cubic.end)
+    }
+}
+
+
+/// Returns the winding number of a closed path of connected 2D curves around point,
+/// which is non-zero if the point lies inside the path (for the non-zero fill rule)
+/// and odd if it lies inside by the even-odd rule.
+/// Counter-clockwise paths yield positive winding numbers.
+/// The number is computed from the signed crossings of the path with the horizontal ray
+/// from point towards positive x. To count crossings at shared end points exactly once,
+/// each curve is split into monotone pieces which include their lower and exclude their upper
+/// end point, and horizontal pieces are ignored. Crossings exactly at point are not counted,
+/// so points on the path are classified like the half-open pixel rules of rasterizers do.
+pub fn winding_number<P, F>(segments: &[CubicBezier<P>], point: P) -> i32
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>
+    + Mul<F, Output = P>,
+F:  Float
+    + Default
+    + Into<NativeFloat>,
+NativeFloat: Sub<F, Output = F>
+    + Add<F, Output = F>
+    + Mul<F, Output = F>
+    + Float
+    + Into<F>
+{
+    debug_assert!(P::DIM == 2);
+    let (x, y) = (point.axis(0), point.axis(1));
+    let mut winding = 0;
+    for segment in segments {
+        for piece in segment.into_monotone::<F>().iter() {
+            let (y0, y1) = (piece.start.axis(1), piece.end.axis(1));
+            if y0 == y1 || y < y0.min(y1) || y >= y0.max(y1) {
+                continue;
+            }
+            // the lower end point is included explicitly as the roots exclude the end points
+            let t: F = if y == y0 {
+                0.0.into()
+            } else if y == y1 {
+                1.0.into()
+            } else {
+                match piece.solve_t_for_axis(y.into(), 1).first() {
+                    Some(t) => *t,
+                    // the root was lost to rounding, so it must be close to one of the end points
+                    None => if (y - y0).abs() < (y - y1).abs() { 0.0.into() } else { 1.0.into() },
+                }
+            };
+            if piece.eval_casteljau(t).axis(0) > x {
+                winding += if y1 > y0 { 1 } else { -1 };
+            }
+        }
+    }
+    winding
+}
+
+
+/// Returns the signed area enclosed by a closed path of connected 2D curves,
+/// which is positive for counter-clockwise paths.
+/// The area is the exact line integral 1/2 ∮ x dy - y dx of Green's theorem,
+/// which has the closed form for a segment with control points p0..p3 (× is the 2D cross product)
+///     3/20 * (2 p0×p1 + p0×p2 + p1×p2 + p1×p3 + 2 p2×p3) + 1/20 p0×p3
+/// If the path is not closed, the result is the area swept by the line from the origin to a point
+/// moving along the path, i.e. the area of the path closed by the straight lines from its end point
+/// to the origin and from the origin to its start point (not by the chord from end to start).
+pub fn signed_area<P, F>(segments: &[CubicBezier<P>]) -> F
+where
+P: Point<Scalar = NativeFloat>
+    + Copy,
+F: Float,
+NativeFloat: Into<F>
+{
+    debug_assert!(P::DIM == 2);
+    let mut area: NativeFloat = 0.0;
+    for s in segments {
+        area += 0.15 * (2.0 * s.start.cross_2d(&s.ctrl1)
+                        + s.start.cross_2d(&s.ctrl2)
+                        + s.ctrl1.cross_2d(&s.ctrl2)
+                        + s.ctrl1.cross_2d(&s.end)
+                        + 2.0 * s.ctrl2.cross_2d(&s.end))
+                + 0.05 * s.start.cross_2d(&s.end);
+    }
+    return area.into()
+}
+
+
+/// Maximum recursion depth of the adaptive quadrature, see CubicBezier::arclen_gauss()
+const ARCLEN_MAX_DEPTH: usize = 16;
+
+/// Number of intervals of the arc length lookup table, see CubicBezier::split_equidistant()
+const ARCLEN_LUT_STEPS: usize = 32;
+
+/// Newton iterations to find the parameter at a given arc length, see CubicBezier::split_equidistant()
+const ARCLEN_NEWTON_STEPS: usize = 4;
+
+/// Abscissae and weights of the 5-point Gauss-Legendre quadrature over [-1, 1]
+const GAUSS_LEGENDRE_5: [(NativeFloat, NativeFloat); 5] = [
+    (0.0, 0.5688888888888889),
+    (-0.5384693101056831, 0.4786286704993665),
+    (0.5384693101056831, 0.4786286704993665),
+    (-0.906179845938664, 0.2369268850561891),
+    (0.906179845938664, 0.2369268850561891),
+];
+
+/// Returns true if the curve b continues the curve a with geometric (G1) continuity:
+/// b starts within 'tolerance' of the end of a and the unit tangent vectors at the join
+/// (see CubicBezier::tangent()) differ by at most 'tolerance', so the direction doesn't jump.
+/// Tangents pointing in opposite directions (a cusp at the join) are not continuous.
+/// Curves without a tangent at the join (all points coincide) are never continuous.
+pub fn is_g1_continuous<P, F>(a: &CubicBezier<P>, b: &CubicBezier<P>, tolerance: F) -> bool
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    let tolerance = tolerance.into();
+    if a.end.distance(&b.start) > tolerance {
+        return false
+    }
+    let (tangent_a, tangent_b) = (a.tangent::<NativeFloat>(1.0), b.tangent::<NativeFloat>(0.0));
+    if tangent_a.squared_length() < EPSILON || tangent_b.squared_length() < EPSILON {
+        return false
+    }
+    tangent_a.distance(&tangent_b) <= tolerance
+}
+
+/// Returns true if the curve b continues the curve a with parametric (C1) continuity:
+/// the join is G1 continuous (see is_g1_continuous()) and the derivatives at the join
+/// also have the same magnitude within 'tolerance' relative to the larger one,
+/// so a point moving along the path with the parameter doesn't change its speed.
+pub fn is_c1_continuous<P, F>(a: &CubicBezier<P>, b: &CubicBezier<P>, tolerance: F) -> bool
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    let tolerance = tolerance.into();
+    if !is_g1_continuous(a, b, tolerance) {
+        return false
+    }
+    let speed_a = a.speed::<NativeFloat>(1.0);
+    let speed_b = b.speed::<NativeFloat>(0.0);
+    (speed_a - speed_b).abs() <= tolerance * speed_a.max(speed_b)
+}
+
+
+/// Roots of the derivative up to this distance outside of [0,1] are still considered
+/// by CubicBezier::bounding_box()
+const BBOX_ROOT_TOLERANCE: NativeFloat = 1e-6;
+
+/// Newton iterations to refine the roots near the end points, see CubicBezier::bounding_box()
+const BBOX_NEWTON_STEPS: usize = 4;
+
+/// Maximum recursion depth when flattening a CubicBezier, see CubicBezier::flattened()
+const FLATTEN_MAX_DEPTH: usize = 16;
+
+/// Turning angle (in radians) below which a piece counts as straight in CubicBezier::flattened_angular()
+#[cfg(feature = "alloc")]
+const FLATTEN_MIN_ANGLE: NativeFloat = 1e3 * EPSILON;
+
+/// Speed relative to the derivative's control points below which a root of the derivative is a cusp,
+/// see CubicBezier::cusp()
+const CUSP_TOLERANCE: NativeFloat = 1e4 * EPSILON;
+
+/// Maximum recursion depth when intersecting two CubicBeziers, see CubicBezier::intersections()
+const INTERSECTION_MAX_DEPTH: usize = 32;
+
+/// Maximum subdivision depth of a piece between inflections, see CubicBezier::offset()
+const OFFSET_MAX_DEPTH: usize = 8;
+
+/// Iterator over the points of a flattened CubicBezier and their parameters, see CubicBezier::flattened()
+/// Uses an explicit, fixed size stack of (sub-curve, depth, parameter of its end point)
+/// for the depth-first subdivision
+struct Flattened<P> {
+    stack: [(CubicBezier<P>, usize, NativeFloat); FLATTEN_MAX_DEPTH + 1],
+    len: usize,
+    tolerance: NativeFloat,
+    emitted_start: bool,
+}
+
+impl<P> Iterator for Flattened<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    type Item = (NativeFloat, P);
+
+    fn next(&mut self) -> Option<(NativeFloat, P)> {
+        if !self.emitted_start {
+            self.emitted_start = true;
+            return Some((0.0, self.stack[0].0.start))
+        }
+        while self.len > 0 {
+            self.len -= 1;
+            let (segment, depth, t_end) = self.stack[self.len];
+            if depth >= FLATTEN_MAX_DEPTH
+                || segment.is_a_point(self.tolerance)
+                || segment.is_linear(self.tolerance)
+            {
+                return Some((t_end, segment.end))
+            }
+            // push the right half first so the left half is processed next
+            // the halves span 2^-(depth+1) of the parameter range, which is exact in floating point
+            let (left, right) = segment.split(0.5);
+            let half_span = (0.5 as NativeFloat).powi(depth as i32 + 1);
+            self.stack[self.len] = (right, depth + 1, t_end);
+            self.stack[self.len + 1] = (left, depth + 1, t_end - half_span);
+            self.len += 2;
+        }
+        None
+    }
+}
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests 
+{
+    use super::*;
+    use super::point_generic::PointN;
+    #[test]
+    fn circle_approximation_error() 
+    {
+        // define closure for unit circle 
+        let circle = |p: PointN<f64, 2>| -> f64 { 
+            p.into_iter().map(|x| x*x).sum::<f64>().sqrt() - 1f64
+        };
+
+        // define control points for 4 bezier segments 
+        // control points are chosen for minimum radial distance error
+        // according to: http://spencermortensen.com/articles/bezier-circle/ 
+        // TODO don't hardcode values
+        let c               = 0.551915024494;
+        let max_drift_perc  = 0.019608; // radial drift percent
+        let max_error       = max_drift_perc * 0.01; // absolute max radial error
+
+        let bezier_quadrant_1= CubicBezier{ 
+                                    start:  PointN::new([0f64, 1f64]),
+                                    ctrl1:  PointN::new([c, 1f64]),
+                                    ctrl2:  PointN::new([1f64, c]),
+                                    end:    PointN::new([1f64, 0f64])
+        };
+        let bezier_quadrant_2 = CubicBezier{ 
+                                    start:  PointN::new([1f64, 0f64]),
+                                    ctrl1:  PointN::new([1f64, -c]),
+                                    ctrl2:  PointN::new([c,  -1f64]),
+                                    end:    PointN::new([0f64, -1f64])
+        };
+        let bezier_quadrant_3 = CubicBezier{ 
+                                    start:  PointN::new([0f64, -1f64]),
+                                    ctrl1:  PointN::new([-c, -1f64]),
+                                    ctrl2:  PointN::new([-1f64, -c]),
+                                    end:    PointN::new([-1f64, 0f64])
+        };
+        let bezier_quadrant_4 = CubicBezier{ 
+                                    start:  PointN::new([-1f64, 0f64]),
+                                    ctrl1:  PointN::new([-1f64, c]),
+                                    ctrl2:  PointN::new([-c, 1f64]),
+                                    end:    PointN::new([0f64, 1f64])
+        };
+        let nsteps =  1000;                                      
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+
+            let point = bezier_quadrant_1.eval(t);
+            let contour = circle(point);
+            assert!( contour.abs() <= max_error );
+
+            let point = bezier_quadrant_2.eval(t);
+            let contour = circle(point);
+            assert!( contour.abs() <= max_error );
+
+            let point = bezier_quadrant_3.eval(t);
+            let contour = circle(point);
+            assert!( contour.abs() <= max_error );
+
+            let point = bezier_quadrant_4.eval(t);
+            let contour = circle(point);
+            assert!( contour.abs() <= max_error );
+        }
+    }
+
+
+    #[test]
+    fn circle_circumference_approximation() 
+    {
+        // define control points for 4 cubic bezier segments to best approximate a unit circle
+        // control points are chosen for minimum radial distance error, see circle_approximation_error() in this file
+        // given this, the circumference will also be close to 2*pi 
+        // (remember arclen also works by linear approximation, not the true integral, so we have to accept error)!
+        // This approximation is unfeasable if desired accuracy is greater than 2 decimal places (at 1000 steps)
+        // TODO don't hardcode values, solve for them
+        let c         = 0.551915024494;
+        let max_error = 1e-2;
+        let nsteps  = 1e3 as usize;
+        let pi        = 3.14159265359;
+        let tau       = 2.*pi;
+
+        let bezier_quadrant_1= CubicBezier{ 
+                                start:  PointN::new([0f64, 1f64]),
+                                ctrl1:  PointN::new([c, 1f64]),
+                                ctrl2:  PointN::new([1f64, c]),
+                                end:    PointN::new([1f64, 0f64])
+        };
+        let bezier_quadrant_2 = CubicBezier{ 
+                                start:  PointN::new([1f64, 0f64]),
+                                ctrl1:  PointN::new([1f64, -c]),
+                                ctrl2:  PointN::new([c, -1f64]),
+                                end:    PointN::new([0f64, -1f64])
+        };
+        let bezier_quadrant_3 = CubicBezier{ 
+                                start:  PointN::new([0f64, -1f64]),
+                                ctrl1:  PointN::new([-c,   -1f64]),
+                                ctrl2:  PointN::new([-1f64, -c]),
+                                end:    PointN::new([-1f64, 0f64])
+        };
+        let bezier_quadrant_4 = CubicBezier{ 
+                                start:  PointN::new([-1f64, 0f64]),
+                                ctrl1:  PointN::new([-1f64, c]),
+                                ctrl2:  PointN::new([-c, 1f64]),
+                                end:    PointN::new([0f64, 1f64])
+        };
+        let circumference = bezier_quadrant_1.arclen::<NativeFloat>(nsteps) +
+                                bezier_quadrant_2.arclen::<NativeFloat>(nsteps) +
+                                bezier_quadrant_3.arclen::<NativeFloat>(nsteps) +
+                                bezier_quadrant_4.arclen::<NativeFloat>(nsteps);
+        //dbg!(circumference);
+        //dbg!(tau);
+        assert!( ((tau + max_error) > circumference) && ((tau - max_error) < circumference) );
+    }
+
+    #[test]
+    fn eval_equivalence() {
+        // all eval methods should be approximately equivalent for well defined test cases
+        // and not equivalent where numerical stability becomes an issue for normal eval
+        let bezier = CubicBezier::new( 
+            PointN::new([0f64,  1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64,3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+
+        let max_err = 1e-14;
+        let nsteps: usize =  1000;                                      
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p1 = bezier.eval(t);
+            let p2 = bezier.eval_casteljau(t);
+            assert!(p1.distance(&p2) < max_err);
+        }
+    }
+
+    #[test]
+    fn split_equivalence() {
+        // chose some arbitrary control points and construct a cubic bezier
+        let bezier = CubicBezier{ 
+                                start:  PointN::new([0f64, 1.77f64]),
+                                ctrl1:  PointN::new([2.9f64, 0f64]),
+                                ctrl2:  PointN::new([4.3f64, 3f64]),
+                                end:    PointN::new([3.2f64, -4f64])
+                            };
+        // split it at an arbitrary point
+        let at = 0.5;
+        let (left, right) = bezier.split(at);
+        // compare left and right subcurves with parent curve
+        // this is tricky as we have to map t->t/2 (for left) which will 
+        // inevitably contain rounding errors from floating point ops.
+        // instead, take the distance of the two points which must not exceed the absolute error
+        let max_err = 1e-14;
+        let nsteps: usize =  1000;                                      
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            //dbg!(bezier.eval(t/2.0));
+            //dbg!(left.eval(t));
+            // left
+            assert!(bezier.eval(t/2.0).distance(&left.eval(t)) < max_err);
+            // right
+            assert!(bezier.eval((t*0.5)+0.5).distance(&right.eval(t)) < max_err);
+        }
+    }
+
+
+    #[test]
+    fn bounding_box_contains() {
+        // check if bounding box for a curve contains all points (with some approximation error)
+        let bezier = CubicBezier{ 
+                        start:  PointN::new([0f64, 1.77f64]),
+                        ctrl1: PointN::new([2.9f64, 0f64]),
+                        ctrl2: PointN::new([4.3f64, -3f64]),
+                        end:   PointN::new([3.2f64, 4f64])
+        };
+
+        let bounds = bezier.bounding_box::<f64>();
+
+        let max_err = 1e-2;
+
+        let nsteps: usize =  100;                                      
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = bezier.eval_casteljau(t);
+            //dbg!(t);
+            //dbg!(p);
+            //dbg!(xmin-max_err, ymin-max_err, xmax+max_err, ymax+max_err);
+            for (idx, axis) in p.into_iter().enumerate() {
+                assert!( (axis >= (bounds[idx].0 - max_err)) && (axis <= (bounds[idx].1 + max_err)) )
+            }
+        }
+    }
+
+
+    #[test]
+    fn control_box_contains_bounding_box() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, -3f64]),
+            PointN::new([3.2f64, 4f64]),
+        );
+        let bounds = bezier.bounding_box::<f64>();
+        let control = bezier.control_box::<f64>();
+        for dim in 0..2 {
+            assert!(control[dim].0 <= bounds[dim].0 && bounds[dim].1 <= control[dim].1);
+        }
+        // the curve doesn't reach the inner control points in y and x
+        assert!(control[1] == (-3.0, 4.0));
+        assert!(bounds[1].0 > -3.0);
+        assert!(control[0] == (0.0, 4.3));
+        assert!(bounds[0].1 < 4.3);
+    }
+
+
+    #[test]
+    fn tangent_and_normal() {
+        // first quadrant of the unit circle approximation, traversed clockwise from (0,1) to (1,0)
+        let c = 0.551915024494;
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1f64]),
+            PointN::new([c, 1f64]),
+            PointN::new([1f64, c]),
+            PointN::new([1f64, 0f64]),
+        );
+        let max_err = 1e-12;
+        // tangent points along +x at the start and along -y at the end
+        let err = bezier.tangent(0.0) - PointN::new([1f64, 0f64]);
+        assert!(err.squared_length() < max_err);
+        let err = bezier.tangent(1.0) - PointN::new([0f64, -1f64]);
+        assert!(err.squared_length() < max_err);
+        // the (counter-clockwise) normal points towards the outside of the circle for this orientation
+        let err = bezier.normal(0.0) - PointN::new([0f64, 1f64]);
+        assert!(err.squared_length() < max_err);
+
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let tangent = bezier.tangent(t);
+            let normal = bezier.normal(t);
+            // both must be unit length and perpendicular to each other
+            assert!((tangent.squared_length() - 1.0).abs() < max_err);
+            assert!((normal.squared_length() - 1.0).abs() < max_err);
+            assert!((tangent.axis(0) * normal.axis(0) + tangent.axis(1) * normal.axis(1)).abs() < max_err);
+        }
+    }
+
+    #[test]
+    fn tangent_degenerate() {
+        // ctrl1 coincides with start, so the first derivative vanishes at t=0
+        // and the tangent direction is given by the second derivative (towards ctrl2)
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([0f64, 0f64]),
+            PointN::new([2f64, 0f64]),
+            PointN::new([3f64, 1f64]),
+        );
+        let err = bezier.tangent(0.0) - PointN::new([1f64, 0f64]);
+        assert!(err.squared_length() < 1e-12);
+
+        // a curve collapsed into a single point has no tangent at all
+        let p = PointN::new([1f64, 1f64]);
+        let point = CubicBezier::new(p, p, p, p);
+        assert!(point.tangent(0.5) == PointN::new([0f64, 0f64]));
+        assert!(point.normal(0.5) == PointN::new([0f64, 0f64]));
+    }
+
+
+    #[test]
+    fn circle_curvature() {
+        // the unit circle approximation must have a curvature (and radius) close to 1 everywhere
+        // the quadrants are traversed clockwise, so the signed curvature is negative
+        // (the optimal radial approximation deviates by up to ~1.93% in curvature)
+        let c = 0.551915024494;
+        let max_error = 2e-2;
+        let quadrants = [
+            CubicBezier::new(PointN::new([0f64, 1f64]), PointN::new([c, 1f64]),
+                            PointN::new([1f64, c]), PointN::new([1f64, 0f64])),
+            CubicBezier::new(PointN::new([1f64, 0f64]), PointN::new([1f64, -c]),
+                            PointN::new([c, -1f64]), PointN::new([0f64, -1f64])),
+            CubicBezier::new(PointN::new([0f64, -1f64]), PointN::new([-c, -1f64]),
+                            PointN::new([-1f64, -c]), PointN::new([-1f64, 0f64])),
+            CubicBezier::new(PointN::new([-1f64, 0f64]), PointN::new([-1f64, c]),
+                            PointN::new([-c, 1f64]), PointN::new([0f64, 1f64])),
+        ];
+        let nsteps: usize = 100;
+        for bezier in quadrants.iter() {
+            for t in 0..=nsteps {
+                let t = t as f64 * 1f64/(nsteps as f64);
+                assert!( (bezier.curvature(t) + 1.0).abs() < max_error );
+                assert!( (bezier.radius(t) + 1.0).abs() < max_error );
+            }
+            // reversing the direction of travel flips the sign
+            let reversed = CubicBezier::new(bezier.end, bezier.ctrl2, bezier.ctrl1, bezier.start);
+            assert!( (reversed.curvature(0.5) - 1.0).abs() < max_error );
+        }
+    }
+
+
+    #[test]
+    fn project_point() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let max_err = 1e-9;
+        // points on the curve must be found with (almost) zero distance
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let (t_proj, dist) = bezier.project::<f64>(bezier.eval(t));
+            assert!(dist < max_err);
+            assert!((t_proj - t).abs() < 1e-6);
+        }
+
+        // a straight line with evenly spaced control points is parametrized by arc length
+        let line = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 0f64]),
+            PointN::new([2f64, 0f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let (t, dist) = line.project::<f64>(PointN::new([1.5f64, 2f64]));
+        assert!((t - 0.5).abs() < max_err);
+        assert!((dist - 2.0).abs() < max_err);
+        // points beyond the ends are projected onto the endpoints
+        let (t, dist) = line.project::<f64>(PointN::new([5f64, 1f64]));
+        assert!((t - 1.0).abs() < max_err);
+        assert!((dist - 5f64.sqrt()).abs() < max_err);
+        let (t, dist) = line.project::<f64>(PointN::new([-4f64, -3f64]));
+        assert!(t.abs() < max_err);
+        assert!((dist - 5.0).abs() < max_err);
+    }
+
+
+    #[test]
+    fn elevate_equivalence() {
+        // the elevated curve must have the exact same shape as the original
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let elevated = bezier.elevate();
+
+        let max_err = 1e-14;
+        let nsteps: usize = 1000;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let err = bezier.eval_casteljau(t) - elevated.eval(t);
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+    }
+
+
+    #[test]
+    fn reduce_roundtrip() {
+        // a cubic obtained by elevating a quadratic must reduce to the very same quadratic
+        let start = PointN::new([0f64, 1.77f64]);
+        let ctrl = PointN::new([4.3f64, 3f64]);
+        let end = PointN::new([3.2f64, -4f64]);
+        let bezier = CubicBezier::new(
+            start,
+            start + (ctrl - start) * (2.0/3.0),
+            end + (ctrl - end) * (2.0/3.0),
+            end,
+        );
+        let (quadratic, err) = bezier.reduce::<f64>();
+        let max_err = 1e-14;
+        assert!(err < max_err);
+        assert!((quadratic.ctrl - ctrl).squared_length() < max_err);
+
+        // a 'real' cubic can't be represented exactly
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let (_, err) = bezier.reduce::<f64>();
+        assert!(err > 0.1);
+    }
+
+
+    #[test]
+    fn flattened_within_tolerance() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        for &tolerance in [1e-1, 1e-2, 1e-3].iter() {
+            let mut points = bezier.flattened(tolerance);
+            let mut prev = points.next().unwrap();
+            assert!(prev == bezier.start);
+            let mut count = 1;
+            for p in points {
+                // the midpoint of every segment must be close to the curve
+                let mid = (prev + p) * 0.5;
+                let (_, dist) = bezier.project::<f64>(mid);
+                assert!(dist <= tolerance);
+                prev = p;
+                count += 1;
             }
-            // add y-values for start and end point as candidates
-            extrema.push(self.start.axis(dim).into()); 
-            extrema.push(self.end.axis(dim).into());
-            // sort to get min and max values for bounding box
-            extrema.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            assert!(prev == bezier.end);
+            assert!(count > 2);
+        }
+        // a straight line needs no subdivision
+        let line = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(line.flattened(1e-3).count() == 2);
+    }
+
+
+    #[test]
+    fn inflections() {
+        // symmetric s-shaped curve with a single inflection in the middle
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, -1f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let inflections = bezier.inflections::<f64>();
+        assert!(inflections.len() == 1);
+        assert!((inflections[0] - 0.5).abs() < 1e-14);
+        // the curvature changes its sign across the inflection
+        assert!(bezier.curvature(0.4) * bezier.curvature(0.6) < 0.0);
+
+        // an asymmetric s-shaped curve
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let inflections = bezier.inflections::<f64>();
+        assert!(inflections.len() == 1);
+        let t = inflections[0];
+        assert!(bezier.curvature(t - 0.01) * bezier.curvature(t + 0.01) < 0.0);
+
+        // a circle segment has no inflections
+        let c = 0.551915024494;
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1f64]),
+            PointN::new([c, 1f64]),
+            PointN::new([1f64, c]),
+            PointN::new([1f64, 0f64]),
+        );
+        assert!(bezier.inflections::<f64>().is_empty());
+    }
+
+
+    #[test]
+    fn split_at_multiple_equivalence() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        // no parameters yields the whole curve
+        let parts = bezier.split_at_multiple::<f64>(&[]);
+        assert!(parts.len() == 1 && parts[0] == bezier);
+
+        // unsorted parameters, one of them out of range
+        let ts = [0.7, 0.2, 1.5, 0.45];
+        let parts = bezier.split_at_multiple(&ts);
+        assert!(parts.len() == 4);
+        let bounds = [0.0, 0.2, 0.45, 0.7, 1.0];
+
+        let max_err = 1e-12;
+        let nsteps: usize = 100;
+        for (i, part) in parts.iter().enumerate() {
+            let (t0, t1) = (bounds[i], bounds[i+1]);
+            for t in 0..=nsteps {
+                let t = t as f64 * 1f64/(nsteps as f64);
+                let err = bezier.eval(t0 + t * (t1 - t0)) - part.eval(t);
+                for axis in err {
+                    assert!(axis.abs() < max_err);
+                }
+            }
+        }
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flattened_angular() {
+        let max_angle = 0.1;
+        let gentle = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 0.2f64]),
+            PointN::new([2f64, 0.2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let tight = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([3f64, 0f64]),
+            PointN::new([3f64, 1f64]),
+            PointN::new([0f64, 1f64]),
+        );
+        let gentle_points = gentle.flattened_angular(max_angle);
+        let tight_points = tight.flattened_angular(max_angle);
+        assert!(tight_points.len() > gentle_points.len());
+
+        for points in [gentle_points, tight_points].iter() {
+            assert!(points.len() > 2);
+            // the chord directions of two adjacent pieces differ by at most their combined turning
+            for w in points.windows(3) {
+                let a = (w[1] - w[0]).normalize();
+                let b = (w[2] - w[1]).normalize();
+                assert!(a.dot(&b).min(1.0).acos() <= 2.0 * max_angle);
+            }
+        }
+        // the number of points doesn't depend on the scale
+        let scaled = tight.transform(&Affine2::scale(100.0, 100.0));
+        assert!(scaled.flattened_angular(max_angle).len() == tight.flattened_angular(max_angle).len());
+
+        // a straight curve is never split, a cusp terminates
+        let straight = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(straight.flattened_angular(0.0).len() == 2);
+        let cusp = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([0f64, 1f64]),
+            PointN::new([1f64, 0f64]),
+        );
+        assert!(cusp.cusp::<f64>().is_some());
+        assert!(cusp.flattened_angular(max_angle).len() <= (1 << FLATTEN_MAX_DEPTH) + 1);
+    }
+
+
+    #[test]
+    fn max_curvature() {
+        // a symmetric bend has its single curvature maximum at the apex at t = 0.5
+        let symmetric = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([4f64, 0f64]),
+        );
+        let t = symmetric.max_curvature::<f64>();
+        assert!((t - 0.5).abs() < 1e-6);
+
+        // the parabola y = x² in x = [-1, 2] has its curvature maximum at the vertex (0,0)
+        // (the cubic is the degree elevated quadratic bezier with x = 3t - 1)
+        let parabola = CubicBezier::from(QuadraticBezier::new(
+            PointN::new([-1f64, 1f64]),
+            PointN::new([0.5f64, -2f64]),
+            PointN::new([2f64, 4f64]),
+        ));
+        let t = parabola.max_curvature::<f64>();
+        assert!((t - 1.0 / 3.0).abs() < 1e-6);
+        assert!(parabola.eval(t).distance(&PointN::new([0f64, 0f64])) < 1e-6);
+        assert!((parabola.curvature(t) - 2.0).abs() < 1e-9);
+
+        // the same parabola starting at the vertex has the peak at the start point
+        let (_, right) = parabola.split(1.0 / 3.0);
+        assert!(right.max_curvature::<f64>() < 1e-6);
+        assert!(right.reverse().max_curvature::<f64>() > 1.0 - 1e-6);
+
+        // the curvature is infinite at a cusp
+        let cusp = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([0f64, 1f64]),
+            PointN::new([1f64, 0f64]),
+        );
+        assert!((cusp.max_curvature::<f64>() - 0.5).abs() < 1e-6);
+    }
+
+
+    #[test]
+    fn g1_c1_continuity() {
+        let a = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 1f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        // the handles at the join are collinear and of the same length
+        let c1 = CubicBezier::new(
+            PointN::new([3f64, 0f64]),
+            PointN::new([4f64, -1f64]),
+            PointN::new([5f64, 0f64]),
+            PointN::new([6f64, 2f64]),
+        );
+        // collinear but twice as long
+        let g1 = CubicBezier::new(
+            PointN::new([3f64, 0f64]),
+            PointN::new([5f64, -2f64]),
+            PointN::new([5f64, 0f64]),
+            PointN::new([6f64, 2f64]),
+        );
+        // a kink at the join
+        let kinked = CubicBezier::new(
+            PointN::new([3f64, 0f64]),
+            PointN::new([4f64, 0f64]),
+            PointN::new([5f64, 0f64]),
+            PointN::new([6f64, 2f64]),
+        );
+        let tolerance = 1e-9;
+        assert!(super::is_g1_continuous(&a, &c1, tolerance) && super::is_c1_continuous(&a, &c1, tolerance));
+        assert!(super::is_g1_continuous(&a, &g1, tolerance) && !super::is_c1_continuous(&a, &g1, tolerance));
+        assert!(!super::is_g1_continuous(&a, &kinked, tolerance) && !super::is_c1_continuous(&a, &kinked, tolerance));
+        // the kink of 45° is continuous only for a tolerance of at least 2 * sin(22.5°)
+        assert!(super::is_g1_continuous(&a, &kinked, 0.77));
+
+        // the halves of a split curve are C1 continuous relative to their own parametrization
+        // only if they are split in the middle
+        let (left, right) = a.split(0.5);
+        assert!(super::is_c1_continuous(&left, &right, tolerance));
+        let (left, right) = a.split(0.3);
+        assert!(super::is_g1_continuous(&left, &right, tolerance) && !super::is_c1_continuous(&left, &right, tolerance));
+
+        // a cusp at the join and disconnected curves
+        assert!(!super::is_g1_continuous(&a, &a.reverse(), tolerance));
+        assert!(!super::is_g1_continuous(&a, &c1.transform(&Affine2::translation(0.0, 1e-3)), tolerance));
+    }
+
+
+    #[test]
+    fn subdivide() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        assert!(bezier.subdivide::<1>() == [bezier]);
+        assert!(bezier.subdivide::<0>().is_empty());
+
+        fn check<const K: usize>(bezier: &CubicBezier<PointN<f64, 2>>) {
+            let parts = bezier.subdivide::<K>();
+            assert!(parts[0].start == bezier.start && parts[K-1].end == bezier.end);
+            let max_err = 1e-12;
+            let nsteps: usize = 20;
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    assert!(parts[i-1].end == part.start);
+                }
+                let t0 = i as f64 / K as f64;
+                for t in 0..=nsteps {
+                    let t = t as f64 * 1f64/(nsteps as f64);
+                    assert!(bezier.eval(t0 + t / K as f64).distance(&part.eval(t)) < max_err);
+                }
+            }
+        }
+        check::<2>(&bezier);
+        check::<4>(&bezier);
+        check::<7>(&bezier);
+        check::<32>(&bezier);
+    }
+
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let json = serde_json::to_string(&bezier).unwrap();
+        assert!(json == r#"{"start":[0.0,1.77],"ctrl1":[1.1,-1.0],"ctrl2":[4.3,3.0],"end":[3.2,-4.0]}"#);
+        let deserialized: CubicBezier<PointN<f64, 2>> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized == bezier);
+
+        // the lower order curves which are returned by derivative() and baseline()
+        let derivative = bezier.derivative::<f64>();
+        let json = serde_json::to_string(&derivative).unwrap();
+        let deserialized: QuadraticBezier<PointN<f64, 2>> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized == derivative);
+        let baseline = bezier.baseline();
+        let json = serde_json::to_string(&baseline).unwrap();
+        let deserialized: LineSegment<PointN<f64, 2>> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized == baseline);
+    }
+
+
+    #[test]
+    fn reverse() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let reversed = bezier.reverse();
+        assert!(reversed.reverse() == bezier);
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(reversed.eval(t).distance(&bezier.eval(1.0 - t)) < max_err);
+        }
+    }
+
+
+    #[test]
+    fn default_is_origin() {
+        let bezier: CubicBezier<PointN<f64, 3>> = CubicBezier::default();
+        let nsteps: usize = 10;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(bezier.eval(t) == PointN::new([0f64; 3]));
+            assert!(bezier.eval_casteljau(t) == PointN::new([0f64; 3]));
+        }
+    }
+
+
+    #[test]
+    fn borrowed_eval() {
+        // a path of two curves sharing the point at index 3
+        let points = [
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+            PointN::new([2.5f64, -6f64]),
+            PointN::new([7.3f64, 2.7f64]),
+            PointN::new([8.9f64, 1.7f64]),
+        ];
+        for i in [0, 3].iter() {
+            let borrowed = CubicBezierRef::new(&points[*i], &points[i + 1], &points[i + 2], &points[i + 3]);
+            let owned = borrowed.to_owned();
+            assert!(owned == CubicBezier::new(points[*i], points[i + 1], points[i + 2], points[i + 3]));
+            assert!(CubicBezierRef::from(&owned) == borrowed);
+            let nsteps: usize = 100;
+            for t in 0..=nsteps {
+                let t = t as f64 * 1f64/(nsteps as f64);
+                assert!(borrowed.eval(t) == owned.eval_casteljau(t));
+            }
+        }
+    }
+
+
+    #[test]
+    fn precompute() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let poly = bezier.precompute();
+        let (_, _, c, d) = poly.coefficients();
+        assert!(d == bezier.start);
+        // the linear coefficient is the derivative at t = 0
+        assert!(c.distance(&bezier.derivative::<f64>().eval(0.0)) < 1e-14);
+
+        let max_err = 1e-14;
+        let nsteps: usize = 1000;
+        let mut first_pass = [PointN::new([0f64; 2]); 1001];
+        for t in 0..=nsteps {
+            let t_f = t as f64 * 1f64/(nsteps as f64);
+            first_pass[t] = poly.eval(t_f);
+            assert!(first_pass[t].distance(&bezier.eval(t_f)) < max_err);
+            assert!(first_pass[t].distance(&bezier.eval_casteljau(t_f)) < max_err);
+        }
+        // repeated evaluation in a hot loop gives identical results
+        for _ in 0..10 {
+            for t in 0..=nsteps {
+                assert!(poly.eval(t as f64 * 1f64/(nsteps as f64)) == first_pass[t]);
+            }
+        }
+        assert!(poly.eval(0.0) == bezier.start);
+        assert!(poly.eval(1.0).distance(&bezier.end) < max_err);
+    }
+
+
+    #[test]
+    fn eval_batch_f32() {
+        // new() requires native points, which are f64 in the test configuration
+        let bezier = CubicBezier {
+            start: PointN::new([0f32, 1.77f32]),
+            ctrl1: PointN::new([1.1f32, -1f32]),
+            ctrl2: PointN::new([4.3f32, 3f32]),
+            end: PointN::new([3.2f32, -4f32]),
+        };
+        // scalar reference with the same control points
+        let reference = CubicBezier::new(
+            bezier.start.map(f64::from),
+            bezier.ctrl1.map(f64::from),
+            bezier.ctrl2.map(f64::from),
+            bezier.end.map(f64::from),
+        );
+        let n = 1000;
+        let mut ts = [0f32; 1000];
+        for (i, t) in ts.iter_mut().enumerate() {
+            *t = i as f32 / (n - 1) as f32;
+        }
+        let mut xs = [0f32; 1000];
+        let mut ys = [0f32; 1000];
+        bezier.eval_batch_f32(&ts, &mut xs, &mut ys);
+        // the error is dominated by the f32 precision
+        let max_err = 1e-5;
+        for i in 0..n {
+            let p = reference.eval(ts[i] as f64);
+            assert!((xs[i] as f64 - p.axis(0)).abs() < max_err);
+            assert!((ys[i] as f64 - p.axis(1)).abs() < max_err);
+        }
+        assert!(xs[0] == 0.0 && ys[0] == 1.77);
+        assert!(xs[n - 1] == 3.2 && ys[n - 1] == -4.0);
+    }
+
+
+    #[test]
+    fn intersections() {
+        // an arch crossed twice by a straight line
+        let arch = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let line = CubicBezier::new(
+            PointN::new([-0.5f64, 1f64]),
+            PointN::new([1f64, 1.1f64]),
+            PointN::new([2f64, 0.9f64]),
+            PointN::new([3.5f64, 1f64]),
+        );
+        let tolerance = 1e-6;
+        let result = arch.intersections(&line, tolerance);
+        assert!(result.len() == 2);
+        for (t_arch, t_line) in result.iter() {
+            assert!(arch.eval(*t_arch).distance(&line.eval(*t_line)) < tolerance);
+        }
+        assert!((result[0].0 - result[1].0).abs() > 0.1);
+        // the result is symmetric
+        let swapped = line.intersections(&arch, tolerance);
+        assert!(swapped.len() == 2);
+
+        // disjoint curves
+        let shifted = CubicBezier::new(
+            PointN::new([0f64, 3f64]),
+            PointN::new([1f64, 5f64]),
+            PointN::new([2f64, 5f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(arch.intersections(&shifted, tolerance).is_empty());
+    }
+
+
+    #[test]
+    fn self_intersection() {
+        let looped = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([3f64, 2f64]),
+            PointN::new([-1f64, 2f64]),
+            PointN::new([2f64, 0f64]),
+        );
+        let (t1, t2) = looped.self_intersection::<f64>().unwrap();
+        assert!(0.0 <= t1 && t1 < t2 && t2 <= 1.0);
+        assert!(t2 - t1 > 0.1);
+        assert!(looped.eval(t1).distance(&looped.eval(t2)) < 1e-12);
+        // the subdivision based intersection of both halves of the loop finds the same point
+        let (left, right) = looped.split(0.5);
+        let crossing = left.intersections(&right, 1e-9);
+        assert!(crossing.iter().any(|(t_left, t_right)|
+            (t_left * 0.5 - t1).abs() < 1e-6 && (0.5 + t_right * 0.5 - t2).abs() < 1e-6));
+
+        let arch = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        assert!(arch.self_intersection::<f64>().is_none());
+        // s-shaped curve with an inflection point
+        let s_curve = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, -2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        assert!(s_curve.self_intersection::<f64>().is_none());
+    }
+
+
+    #[test]
+    fn offset() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 0.5f64]),
+            PointN::new([2f64, -0.3f64]),
+            PointN::new([3f64, 0.2f64]),
+        );
+        let distance = 0.25;
+        let tolerance = 1e-3;
+        for d in [distance, -distance].iter() {
+            let offset = bezier.offset(*d, tolerance);
+            // the refinement converged before running out of capacity
+            assert!(!offset.is_empty() && offset.len() < 16);
+            // the pieces are connected and start/end at the offset end points
+            assert!(offset[0].start.distance(&(bezier.start + bezier.normal(0.0) * *d)) < EPSILON);
+            assert!(offset[offset.len()-1].end.distance(&(bezier.end + bezier.normal(1.0) * *d)) < EPSILON);
+            for pair in offset.windows(2) {
+                assert!(pair[0].end.distance(&pair[1].start) < 1e-12);
+            }
+            // every sampled point of the offset is (approximately) distance away from the curve
+            let nsteps: usize = 50;
+            for piece in offset.iter() {
+                for t in 0..=nsteps {
+                    let t = t as f64 * 1f64/(nsteps as f64);
+                    let p = piece.eval(t);
+                    let (t_proj, dist): (f64, f64) = bezier.project(p);
+                    assert!((dist - distance).abs() < 2.0 * tolerance);
+                    // and on the side the normal points to
+                    let side = (p - bezier.eval(t_proj)).dot(&bezier.normal(t_proj));
+                    assert!(side * *d > 0.0);
+                }
+            }
+        }
+    }
+
+
+    #[test]
+    fn extrema() {
+        // x(t) = 9t(1-t) has its maximum at t=1/2, y(t) = 9t^2(1-t) at t=2/3
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([3f64, 0f64]),
+            PointN::new([3f64, 3f64]),
+            PointN::new([0f64, 0f64]),
+        );
+        let extrema = bezier.extrema::<f64>();
+        assert!(extrema.len() == 2);
+        assert!((extrema[0] - 0.5).abs() < 1e-12);
+        assert!((extrema[1] - 2.0/3.0).abs() < 1e-12);
+        let derivative = bezier.derivative::<f64>();
+        assert!(derivative.eval(extrema[0]).axis(0).abs() < 1e-12);
+        assert!(derivative.eval(extrema[1]).axis(1).abs() < 1e-12);
+
+        // a monotone curve has no extrema
+        let monotone = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 0.5f64]),
+            PointN::new([2f64, 2.5f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(monotone.extrema::<f64>().is_empty());
+
+        // the fourth axis (with its maximum at t=1/2) is ignored
+        let bezier_4d = CubicBezier::new(
+            PointN::new([0f64, 0f64, 0f64, 0f64]),
+            PointN::new([1f64, 0.5f64, 1f64, 3f64]),
+            PointN::new([2f64, 2.5f64, 2f64, 3f64]),
+            PointN::new([3f64, 3f64, 3f64, 0f64]),
+        );
+        assert!(bezier_4d.extrema::<f64>().is_empty());
+    }
+
+
+    #[test]
+    fn into_monotone() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let pieces = bezier.into_monotone::<f64>();
+        assert!(pieces.len() == bezier.extrema::<f64>().len() + 1);
+        assert!(pieces[0].start == bezier.start && pieces[pieces.len()-1].end == bezier.end);
+
+        let max_err = 1e-12;
+        let nsteps: usize = 100;
+        for piece in pieces.iter() {
+            for axis in 0..2 {
+                let direction = piece.end.axis(axis) - piece.start.axis(axis);
+                let mut prev = piece.start.axis(axis);
+                for t in 1..=nsteps {
+                    let t = t as f64 * 1f64/(nsteps as f64);
+                    let value = piece.eval(t).axis(axis);
+                    assert!((value - prev) * direction.signum() >= -max_err);
+                    prev = value;
+                }
+            }
+        }
+    }
+
+
+    #[test]
+    fn arclen_gauss_converges() {
+        // first quadrant of the unit circle approximation
+        let c = 0.551915024494;
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1f64]),
+            PointN::new([c, 1f64]),
+            PointN::new([1f64, c]),
+            PointN::new([1f64, 0f64]),
+        );
+        // length of this bezier curve (integrated with arbitrary precision),
+        // which is slightly longer than the quarter circle
+        let quadrant_len = 1.5708561799040759;
+        assert!((bezier.arclen_gauss(1e-6) - quadrant_len).abs() < 1e-6);
+        assert!((bezier.arclen_gauss(1e-12) - quadrant_len).abs() < 1e-12);
+        assert!((bezier.arclen_gauss(1e-6) - core::f64::consts::FRAC_PI_2).abs() < 1e-4);
+        // the polyline approximation is always shorter
+        assert!(bezier.arclen::<f64>(1000) < bezier.arclen_gauss(1e-12));
+
+        // degenerate curve
+        let point = PointN::new([1f64, 2f64]);
+        assert!(CubicBezier::new(point, point, point, point).arclen_gauss(1e-6) == 0.0);
+    }
+
+
+    #[test]
+    fn eval_batch() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, -3f64]),
+            PointN::new([3.2f64, 4f64]),
+        );
+        let nsteps: usize = 100;
+        let mut ts = [0f64; 100];
+        for i in 0..nsteps {
+            // unevenly spaced, including both endpoints
+            let t = i as f64 * 1f64/((nsteps - 1) as f64);
+            ts[i] = t * t;
+        }
+        let mut out = [PointN::new([0f64; 2]); 100];
+        bezier.eval_batch(&ts, &mut out);
+        let max_err = 1e-12;
+        for (t, p) in ts.iter().zip(out.iter()) {
+            assert!(p.distance(&bezier.eval_casteljau(*t)) < max_err);
+        }
+        // repeated parameters
+        let ts = [0.2f64, 0.5, 0.5, 0.5, 0.7, 0.7];
+        let mut out = [PointN::new([0f64; 2]); 6];
+        bezier.eval_batch(&ts, &mut out);
+        for (t, p) in ts.iter().zip(out.iter()) {
+            assert!(p.distance(&bezier.eval_casteljau(*t)) < max_err);
+        }
+        // empty batch
+        bezier.eval_batch::<f64>(&[], &mut []);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display() {
+        use alloc::format;
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        assert!(format!("{}", bezier) == "[(0, 1.77), (1.1, -1), (4.3, 3), (3.2, -4)]");
+        assert!(format!("{:.1}", bezier) == "[(0.0, 1.8), (1.1, -1.0), (4.3, 3.0), (3.2, -4.0)]");
+    }
+
+
+    #[test]
+    fn tangent_angle_and_turning() {
+        // upper half of the unit circle from (1,0) to (-1,0), counter-clockwise
+        // as two quadrant approximations, the second one crosses the discontinuity of atan2 at π
+        let c = 0.551915024494;
+        let first = CubicBezier::new(
+            PointN::new([1f64, 0f64]),
+            PointN::new([1f64, c]),
+            PointN::new([c, 1f64]),
+            PointN::new([0f64, 1f64]),
+        );
+        let second = CubicBezier::new(
+            PointN::new([0f64, 1f64]),
+            PointN::new([-c, 1f64]),
+            PointN::new([-1f64, c]),
+            PointN::new([-1f64, 0f64]),
+        );
+        let pi = core::f64::consts::PI;
+        let max_err = 1e-12;
+        assert!((first.tangent_angle(0.0) - pi / 2.0).abs() < max_err);
+        assert!((first.tangent_angle(1.0) - pi).abs() < max_err);
+        assert!((second.tangent_angle(0.0).abs() - pi).abs() < max_err);
+        assert!((second.tangent_angle(1.0) + pi / 2.0).abs() < max_err);
+
+        let nsteps = 100;
+        let turning = first.total_turning::<f64>(nsteps) + second.total_turning::<f64>(nsteps);
+        assert!((turning - pi).abs() < max_err);
+        assert!((second.total_turning::<f64>(nsteps) - pi / 2.0).abs() < max_err);
+        // the reversed curve turns clockwise
+        assert!((second.reverse().total_turning::<f64>(nsteps) + pi / 2.0).abs() < max_err);
+        // a straight line doesn't turn
+        let line = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(line.total_turning::<f64>(nsteps).abs() < max_err);
+    }
+
+
+    #[test]
+    fn split_equidistant() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, -3f64]),
+            PointN::new([3.2f64, 4f64]),
+        );
+        let total = bezier.arclen_gauss(1e-12);
+        for n in [1, 2, 5, 16].iter() {
+            let pieces = bezier.split_equidistant(*n);
+            assert!(pieces.len() == *n);
+            assert!(pieces[0].start == bezier.start && pieces[n - 1].end == bezier.end);
+            for piece in pieces.iter() {
+                assert!((piece.arclen_gauss(1e-12) - total / *n as f64).abs() < 1e-9);
+            }
+            for pair in pieces.windows(2) {
+                assert!(pair[0].end == pair[1].start);
+            }
+        }
+
+        // degenerate curves (a point and a curve with its length concentrated at the start)
+        // must not produce NaN control points
+        let p = PointN::new([1f64, 1f64]);
+        let q = PointN::new([2f64, 1f64]);
+        for curve in [CubicBezier::new(p, p, p, p), CubicBezier::new(p, q, q, q)].iter() {
+            for piece in curve.split_equidistant(16).iter() {
+                for c in [piece.start, piece.ctrl1, piece.ctrl2, piece.end].iter() {
+                    assert!(c.axis(0).is_finite() && c.axis(1).is_finite());
+                }
+            }
+        }
+    }
+
+
+    #[test]
+    fn ray_intersections() {
+        // y(t) = 6t(1-t)(1-2t) and x(t) = 3t
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, -2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let max_err = 1e-9;
+        // the line y=-0.1 crosses the curve twice, the ray only once
+        let origin = PointN::new([2.5f64, -0.1f64]);
+        let ts = bezier.ray_intersections::<f64>(origin, PointN::new([2f64, 0f64]));
+        assert!(ts.len() == 1);
+        let p = bezier.eval(ts[0]);
+        assert!((p.axis(1) + 0.1).abs() < max_err && p.axis(0) > 2.5);
+        // in the opposite direction, the other crossing remains
+        let ts = bezier.ray_intersections::<f64>(origin, PointN::new([-1f64, 0f64]));
+        assert!(ts.len() == 1);
+        let p = bezier.eval(ts[0]);
+        assert!((p.axis(1) + 0.1).abs() < max_err && p.axis(0) < 2.5);
+        // a ray in front of the curve crosses it twice at y=0.1
+        let ts = bezier.ray_intersections::<f64>(PointN::new([-1f64, 0.1f64]), PointN::new([1f64, 0f64]));
+        assert!(ts.len() == 2 && ts[0] < ts[1]);
+        for t in ts.iter() {
+            assert!((bezier.eval(*t).axis(1) - 0.1).abs() < max_err);
+        }
+        // an oblique ray through (1.5, 0) which is at t=0.5
+        let ts = bezier.ray_intersections::<f64>(PointN::new([1f64, -1f64]), PointN::new([0.5f64, 1f64]));
+        assert!(ts.len() == 1 && (ts[0] - 0.5).abs() < max_err);
+        // no direction
+        assert!(bezier.ray_intersections::<f64>(origin, PointN::new([0f64, 0f64])).is_empty());
+    }
+
+
+    #[test]
+    fn signed_area() {
+        // unit circle approximation, counter-clockwise
+        let c = 0.551915024494;
+        let circle = [
+            CubicBezier::new(PointN::new([1f64, 0f64]), PointN::new([1f64, c]),
+                             PointN::new([c, 1f64]), PointN::new([0f64, 1f64])),
+            CubicBezier::new(PointN::new([0f64, 1f64]), PointN::new([-c, 1f64]),
+                             PointN::new([-1f64, c]), PointN::new([-1f64, 0f64])),
+            CubicBezier::new(PointN::new([-1f64, 0f64]), PointN::new([-1f64, -c]),
+                             PointN::new([-c, -1f64]), PointN::new([0f64, -1f64])),
+            CubicBezier::new(PointN::new([0f64, -1f64]), PointN::new([c, -1f64]),
+                             PointN::new([1f64, -c]), PointN::new([1f64, 0f64])),
+        ];
+        let pi = core::f64::consts::PI;
+        // the approximation is slightly larger than the circle
+        let area = super::signed_area::<_, f64>(&circle);
+        assert!(area > pi && area - pi < 3e-4);
+        // clockwise paths have negative area, independent of the position
+        let m = Affine2::translation(3.0, -7.0);
+        let reversed = [circle[3].reverse().transform(&m), circle[2].reverse().transform(&m),
+                        circle[1].reverse().transform(&m), circle[0].reverse().transform(&m)];
+        assert!((super::signed_area::<_, f64>(&reversed) + area).abs() < 1e-12);
+
+        // exact for polygons with straight segments
+        let corners = [[0f64, 0f64], [2f64, 0f64], [2f64, 3f64], [0f64, 3f64]];
+        let mut square = [CubicBezier::default(); 4];
+        for i in 0..4 {
+            let start = PointN::new(corners[i]);
+            let end = PointN::new(corners[(i + 1) % 4]);
+            square[i] = CubicBezier::new(start, start.interpolate(&end, 1.0/3.0),
+                                         start.interpolate(&end, 2.0/3.0), end);
+        }
+        assert!((super::signed_area::<_, f64>(&square) - 6.0).abs() < 1e-12);
+        assert!(super::signed_area::<PointN<f64, 2>, f64>(&[]) == 0.0);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn curvature_comb() {
+        // symmetric s-curve with an inflection at t=0.5
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, -2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        let scale = 0.5;
+        let comb = bezier.curvature_comb(10, scale);
+        assert!(comb.len() == 11);
+        for (i, (p, tip)) in comb.iter().enumerate() {
+            let t = i as f64 * 1f64/10f64;
+            assert!(p.distance(&bezier.eval_casteljau(t)) < 1e-12);
+            assert!((p.distance(tip) - (scale * bezier.curvature(t)).abs()).abs() < 1e-12);
+        }
+        // no comb at the inflection, on opposite sides of the curve before (turning clockwise) and after
+        assert!(comb[5].0.distance(&comb[5].1) < 1e-12);
+        let side = |i: usize| bezier.tangent(i as f64 * 0.1).cross_2d(&(comb[i].1 - comb[i].0));
+        assert!(side(2) < 0.0 && side(8) > 0.0);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flattened_with_t() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let tolerance = 1e-3;
+        let polyline = bezier.flattened_with_t(tolerance);
+        assert!(polyline[0] == (0.0, bezier.start));
+        assert!(polyline[polyline.len() - 1] == (1.0, bezier.end));
+        for pair in polyline.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+        }
+        for (t, p) in polyline.iter() {
+            assert!(bezier.eval_casteljau(*t).distance(p) < 1e-12);
+        }
+        // same points as flattened()
+        assert!(polyline.iter().map(|(_, p)| *p).eq(bezier.flattened(tolerance)));
+    }
 
 
-            // determine xmin, xmax, ymin, ymax, from the set {B(xroots), B(yroots), B(0), B(1)} 
-            // (Intermediate control points can't form a boundary)
-            // .unwrap() is ok as it can never be empty as it always at least contains the endpoints
-            bounds[dim] = (extrema[0], *extrema.last().unwrap());
+    #[test]
+    fn bounding_box_double_root() {
+        // y(t) = 0.1 * (3t - 1)^3, so the derivative in y has a double root at t=1/3
+        // (a stationary inflection) and x(t) = 1 - (3t - 1)^2 has its maximum at t=1/3.
+        // 1/3 isn't representable, so the discriminant of the derivative is only zero up to rounding
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, -0.1f64]),
+            PointN::new([2f64, 0.2f64]),
+            PointN::new([1f64, -0.4f64]),
+            PointN::new([-3f64, 0.8f64]),
+        );
+        let extrema = bezier.axis_extrema::<f64>(1, 0.0);
+        assert!(extrema.len() == 1 && (extrema[0] - 1.0 / 3.0).abs() < 1e-7);
+        let extrema = bezier.extrema::<f64>();
+        assert!(!extrema.is_empty() && extrema.iter().all(|t| (t - 1.0 / 3.0).abs() < 1e-7));
+
+        let bounds = bezier.bounding_box::<f64>();
+        assert!((bounds[0].0 + 3.0).abs() < 1e-12 && (bounds[0].1 - 1.0).abs() < 1e-12);
+        assert!((bounds[1].0 + 0.1).abs() < 1e-12 && (bounds[1].1 - 0.8).abs() < 1e-12);
+    }
+
+
+    #[test]
+    fn bounding_box_extremum_at_end() {
+        // y'(t) = k * (t - 0.2) * (t - r) with a maximum of y at r just before t=1,
+        // given by the bernstein coefficients of the derivative
+        for &r in [1.0 - 1e-9, 1.0, 1.0 + 1e-9].iter() {
+            let k = -3.0;
+            let (d0, d2) = (k * 0.2 * r, k * 0.8 * (1.0 - r));
+            let d1 = (4.0 * k * (0.5 - 0.2) * (0.5 - r) - d0 - d2) / 2.0;
+            let bezier = CubicBezier::new(
+                PointN::new([0f64, 0f64]),
+                PointN::new([1f64, d0 / 3.0]),
+                PointN::new([2f64, (d0 + d1) / 3.0]),
+                PointN::new([3f64, (d0 + d1 + d2) / 3.0]),
+            );
+            // the root near 1 is kept and refined inside of [0,1]
+            let extrema = bezier.axis_extrema::<f64>(1, BBOX_ROOT_TOLERANCE);
+            assert!(extrema.len() == 2);
+            assert!((extrema[1] - r.min(1.0)).abs() < 1e-12 && extrema[1] <= 1.0);
+            // without the tolerance, the root is dropped if it's not strictly inside
+            assert!(bezier.axis_extrema::<f64>(1, 0.0).len() == if r < 1.0 { 2 } else { 1 });
+
+            let bounds = bezier.bounding_box::<f64>();
+            assert!(bounds[1].1 >= bezier.eval_casteljau(r.min(1.0)).axis(1));
+            assert!(bounds[1].1 >= bezier.end.axis(1));
+            assert!((bounds[1].0 - bezier.eval_casteljau(0.2).axis(1)).abs() < 1e-12);
         }
-        return bounds
+
+        // the maximum of y lies strictly inside, but so close to the end point that
+        // the root of the derivative is rounded beyond t=1
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, -2f64]),
+            PointN::new([1f64, -0.1f64]),
+            PointN::new([2f64, 2f64.powi(-53)]),
+            PointN::new([3f64, 0f64]),
+        );
+        let t_max = 0.9999999999999994;
+        let y_max = bezier.eval_casteljau(t_max).axis(1);
+        assert!(y_max > bezier.start.axis(1) && y_max > bezier.end.axis(1));
+        // the strict interval drops the root, so the box only covered the end points
+        assert!(bezier.axis_extrema::<f64>(1, 0.0).is_empty());
+        assert!(bezier.bounding_box::<f64>()[1].1 >= y_max);
     }
 
-}
+
+    #[test]
+    fn solve_t_for_value() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, -3f64]),
+            PointN::new([3.2f64, 4f64]),
+        );
+        let max_err = 1e-12;
+        // the vertical line x=3.5 is crossed twice as x reaches its maximum in between
+        let ts = bezier.solve_t_for_value(3.5f64, 0);
+        assert!(ts.len() == 2 && ts[0] < ts[1]);
+        for t in ts.iter() {
+            assert!(*t > 0.0 && *t < 1.0);
+            assert!((bezier.eval(*t).axis(0) - 3.5).abs() < max_err);
+        }
+        // the horizontal line y=0 is crossed twice
+        let ts = bezier.solve_t_for_value(0f64, 1);
+        assert!(ts.len() == 2);
+        for t in ts.iter() {
+            assert!(bezier.eval(*t).axis(1).abs() < max_err);
+        }
+        // outside of the curve's range
+        assert!(bezier.solve_t_for_value(5f64, 0).is_empty());
+    }
 
 
-#[cfg(test)]
-mod tests 
-{
-    use super::*;
-    use super::point_generic::PointN;
     #[test]
-    fn circle_approximation_error() 
-    {
-        // define closure for unit circle 
-        let circle = |p: PointN<f64, 2>| -> f64 { 
-            p.into_iter().map(|x| x*x).sum::<f64>().sqrt() - 1f64
-        };
+    fn clip_to_rect() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([2.9f64, 0f64]),
+            PointN::new([4.3f64, -3f64]),
+            PointN::new([3.2f64, 4f64]),
+        );
+        let max_err = 1e-12;
+        // the curve enters the rectangle through the left and leaves it through the bottom
+        let (min, max) = (PointN::new([1f64, -0.3f64]), PointN::new([5f64, 5f64]));
+        let intervals = bezier.clip_to_rect::<f64>(min, max);
+        assert!(intervals.len() == 2);
+        // ... and re-enters through the bottom until its end
+        let (t_in, t_out) = intervals[0];
+        assert!((bezier.eval(t_in).axis(0) - 1.0).abs() < max_err);
+        assert!((bezier.eval(t_out).axis(1) + 0.3).abs() < max_err);
+        assert!((bezier.eval(intervals[1].0).axis(1) + 0.3).abs() < max_err);
+        assert!(intervals[1].1 == 1.0);
 
-        // define control points for 4 bezier segments 
-        // control points are chosen for minimum radial distance error
-        // according to: http://spencermortensen.com/articles/bezier-circle/ 
-        // TODO don't hardcode values
-        let c               = 0.551915024494;
-        let max_drift_perc  = 0.019608; // radial drift percent
-        let max_error       = max_drift_perc * 0.01; // absolute max radial error
+        // entering and leaving once through the top
+        let (min, max) = (PointN::new([1f64, -5f64]), PointN::new([5f64, 2f64]));
+        let intervals = bezier.clip_to_rect::<f64>(min, max);
+        assert!(intervals.len() == 1);
+        let (t_in, t_out) = intervals[0];
+        assert!((bezier.eval(t_in).axis(0) - 1.0).abs() < max_err);
+        assert!((bezier.eval(t_out).axis(1) - 2.0).abs() < max_err);
+        for t in 0..=100 {
+            let t = t as f64 * 1f64/100f64;
+            let p = bezier.eval(t);
+            let inside = p.axis(0) >= 1.0 && p.axis(1) <= 2.0;
+            assert!(inside == (t >= t_in && t <= t_out));
+        }
 
-        let bezier_quadrant_1= CubicBezier{ 
-                                    start:  PointN::new([0f64, 1f64]),
-                                    ctrl1:  PointN::new([c, 1f64]),
-                                    ctrl2:  PointN::new([1f64, c]),
-                                    end:    PointN::new([1f64, 0f64])
-        };
-        let bezier_quadrant_2 = CubicBezier{ 
-                                    start:  PointN::new([1f64, 0f64]),
-                                    ctrl1:  PointN::new([1f64, -c]),
-                                    ctrl2:  PointN::new([c,  -1f64]),
-                                    end:    PointN::new([0f64, -1f64])
-        };
-        let bezier_quadrant_3 = CubicBezier{ 
-                                    start:  PointN::new([0f64, -1f64]),
-                                    ctrl1:  PointN::new([-c, -1f64]),
-                                    ctrl2:  PointN::new([-1f64, -c]),
-                                    end:    PointN::new([-1f64, 0f64])
-        };
-        let bezier_quadrant_4 = CubicBezier{ 
-                                    start:  PointN::new([-1f64, 0f64]),
-                                    ctrl1:  PointN::new([-1f64, c]),
-                                    ctrl2:  PointN::new([-c, 1f64]),
-                                    end:    PointN::new([0f64, 1f64])
-        };
-        let nsteps =  1000;                                      
-        for t in 0..=nsteps {
-            let t = t as f64 * 1f64/(nsteps as f64);
+        // fully inside and fully outside
+        let intervals = bezier.clip_to_rect::<f64>(PointN::new([-1f64, -5f64]), PointN::new([5f64, 5f64]));
+        assert!(intervals.len() == 1 && intervals[0] == (0.0, 1.0));
+        assert!(bezier.clip_to_rect::<f64>(PointN::new([6f64, 6f64]), PointN::new([7f64, 7f64])).is_empty());
+    }
 
-            let point = bezier_quadrant_1.eval(t);
-            let contour = circle(point);
-            assert!( contour.abs() <= max_error );
 
-            let point = bezier_quadrant_2.eval(t);
-            let contour = circle(point);
-            assert!( contour.abs() <= max_error );
+    #[test]
+    fn y_for_x_easing() {
+        // css ease-in-out
+        let ease = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([0.42f64, 0f64]),
+            PointN::new([0.58f64, 1f64]),
+            PointN::new([1f64, 1f64]),
+        );
+        let max_err = 1e-12;
+        assert!(ease.y_for_x(0f64) == Some(0.0));
+        assert!(ease.y_for_x(1f64) == Some(1.0));
+        assert!((ease.y_for_x(0.5f64).unwrap() - 0.5).abs() < max_err);
+        let mut prev = 0.0;
+        for x in 1..20 {
+            let x = x as f64 * 1f64/20f64;
+            let y = ease.y_for_x(x).unwrap();
+            // the curve is symmetric around (0.5, 0.5) and increasing
+            assert!((y + ease.y_for_x(1.0 - x).unwrap() - 1.0).abs() < max_err);
+            assert!(y > prev);
+            prev = y;
+            // the point at the parameter found for x matches
+            let t = ease.solve_t_for_value(x, 0)[0];
+            assert!((ease.eval(t).axis(0) - x).abs() < max_err);
+            assert!((ease.eval(t).axis(1) - y).abs() < max_err);
+        }
+        // slow start and end
+        assert!(ease.y_for_x(0.1f64).unwrap() < 0.1 && ease.y_for_x(0.9f64).unwrap() > 0.9);
+        assert!(ease.y_for_x(-0.1f64).is_none());
+        assert!(ease.y_for_x(1.1f64).is_none());
+    }
 
-            let point = bezier_quadrant_3.eval(t);
-            let contour = circle(point);
-            assert!( contour.abs() <= max_error );
 
-            let point = bezier_quadrant_4.eval(t);
-            let contour = circle(point);
-            assert!( contour.abs() <= max_error );
+    #[test]
+    fn length_bounds() {
+        // first quadrant of the unit circle approximation
+        let c = 0.551915024494;
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1f64]),
+            PointN::new([c, 1f64]),
+            PointN::new([1f64, c]),
+            PointN::new([1f64, 0f64]),
+        );
+        let arclen = bezier.arclen_gauss(1e-12);
+        let polygon = bezier.control_polygon_length::<f64>();
+        let chord = bezier.chord_length::<f64>();
+        assert!(polygon > core::f64::consts::FRAC_PI_2 && polygon > arclen);
+        assert!((polygon - (2.0 * c + (2.0 * (1.0 - c) * (1.0 - c)).sqrt())).abs() < 1e-12);
+        assert!(chord < arclen && (chord - 2f64.sqrt()).abs() < 1e-12);
+        // both are equal for straight curves with the control points in order
+        let line = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!((line.control_polygon_length::<f64>() - line.chord_length::<f64>()).abs() < 1e-12);
+    }
+
+
+    #[test]
+    fn flatness() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let mut curve = bezier;
+        let mut prev = curve.flatness::<f64>();
+        for _ in 0..10 {
+            curve = curve.split(0.5).0;
+            let flatness = curve.flatness::<f64>();
+            assert!(flatness < prev);
+            prev = flatness;
+            // the curve stays within the bound of its chord
+            let max_dist = flatness.sqrt() / 4.0;
+            for t in 0..=100 {
+                let t = t as f64 * 1f64/100f64;
+                assert!(curve.baseline().distance_to_point::<f64>(curve.eval(t)) <= max_dist + 1e-12);
+            }
         }
+        // ... and eventually shrinks by a factor of 16 per split
+        let ratio = curve.split(0.5).0.flatness::<f64>() / prev;
+        assert!((ratio - 1.0/16.0).abs() < 1e-2);
+        // straight lines with uniformly spaced control points are perfectly flat
+        let line = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 3f64]),
+        );
+        assert!(line.flatness::<f64>() < 1e-24);
     }
 
 
     #[test]
-    fn circle_circumference_approximation() 
-    {
-        // define control points for 4 cubic bezier segments to best approximate a unit circle
-        // control points are chosen for minimum radial distance error, see circle_approximation_error() in this file
-        // given this, the circumference will also be close to 2*pi 
-        // (remember arclen also works by linear approximation, not the true integral, so we have to accept error)!
-        // This approximation is unfeasable if desired accuracy is greater than 2 decimal places (at 1000 steps)
-        // TODO don't hardcode values, solve for them
-        let c         = 0.551915024494;
-        let max_error = 1e-2;
-        let nsteps  = 1e3 as usize;
-        let pi        = 3.14159265359;
-        let tau       = 2.*pi;
+    fn speed() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64,  1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        // integrate the speed with simpson's rule
+        let nsteps: usize = 1000;
+        let h = 1f64/(nsteps as f64);
+        let mut integral = 0f64;
+        for t in 0..=nsteps {
+            let weight = if t == 0 || t == nsteps { 1.0 } else if t % 2 == 1 { 4.0 } else { 2.0 };
+            let speed: f64 = bezier.speed(t as f64 * h);
+            assert!(speed >= 0.0);
+            integral += weight * speed;
+        }
+        integral *= h / 3.0;
+        assert!((integral - bezier.arclen_gauss(1e-12)).abs() < 1e-9);
+        assert!((integral - bezier.arclen::<f64>(10000)).abs() < 1e-5);
 
-        let bezier_quadrant_1= CubicBezier{ 
-                                start:  PointN::new([0f64, 1f64]),
-                                ctrl1:  PointN::new([c, 1f64]),
-                                ctrl2:  PointN::new([1f64, c]),
-                                end:    PointN::new([1f64, 0f64])
-        };
-        let bezier_quadrant_2 = CubicBezier{ 
-                                start:  PointN::new([1f64, 0f64]),
-                                ctrl1:  PointN::new([1f64, -c]),
-                                ctrl2:  PointN::new([c, -1f64]),
-                                end:    PointN::new([0f64, -1f64])
-        };
-        let bezier_quadrant_3 = CubicBezier{ 
-                                start:  PointN::new([0f64, -1f64]),
-                                ctrl1:  PointN::new([-c,   -1f64]),
-                                ctrl2:  PointN::new([-1f64, -c]),
-                                end:    PointN::new([-1f64, 0f64])
-        };
-        let bezier_quadrant_4 = CubicBezier{ 
-                                start:  PointN::new([-1f64, 0f64]),
-                                ctrl1:  PointN::new([-1f64, c]),
-                                ctrl2:  PointN::new([-c, 1f64]),
-                                end:    PointN::new([0f64, 1f64])
-        };
-        let circumference = bezier_quadrant_1.arclen::<NativeFloat>(nsteps) +
-                                bezier_quadrant_2.arclen::<NativeFloat>(nsteps) +
-                                bezier_quadrant_3.arclen::<NativeFloat>(nsteps) +
-                                bezier_quadrant_4.arclen::<NativeFloat>(nsteps);
-        //dbg!(circumference);
-        //dbg!(tau);
-        assert!( ((tau + max_error) > circumference) && ((tau - max_error) < circumference) );
+        // cusp at t = 0.5
+        let cusp = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([0f64, 1f64]),
+            PointN::new([1f64, 0f64]),
+        );
+        assert!(cusp.speed(0.5) < 1e-12);
+        assert!(cusp.speed(0.4) > 0.1);
     }
 
     #[test]
-    fn eval_equivalence() {
-        // all eval methods should be approximately equivalent for well defined test cases
-        // and not equivalent where numerical stability becomes an issue for normal eval
-        let bezier = CubicBezier::new( 
+    fn cusp() {
+        // the inner control points are swapped around the chord, so the derivative vanishes at t = 0.5
+        let cusp = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 1f64]),
+            PointN::new([0f64, 1f64]),
+            PointN::new([1f64, 0f64]),
+        );
+        let t: f64 = cusp.cusp().unwrap();
+        assert!((t - 0.5).abs() < 1e-12);
+        let transformed = cusp.transform(&Affine2::rotation(0.7));
+        let t: f64 = transformed.cusp().unwrap();
+        assert!((t - 0.5).abs() < 1e-12);
+
+        // a small loop is close to, but not a cusp
+        let looped = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1.01f64, 1f64]),
+            PointN::new([-0.01f64, 1f64]),
+            PointN::new([1f64, 0f64]),
+        );
+        assert!(looped.cusp::<f64>().is_none());
+        assert!(looped.self_intersection::<f64>().is_some());
+        // regular curves, the derivative of a single axis vanishes at extrema
+        let bezier = CubicBezier::new(
             PointN::new([0f64,  1.77f64]),
             PointN::new([1.1f64, -1f64]),
-            PointN::new([4.3f64,3f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        assert!(bezier.cusp::<f64>().is_none());
+        let point = CubicBezier::new(cusp.start, cusp.start, cusp.start, cusp.start);
+        assert!(point.cusp::<f64>().is_none());
+
+        // a control point coinciding with its end point makes the derivative vanish there
+        let a = PointN::new([0f64, 0f64]);
+        let b = PointN::new([1f64, 2f64]);
+        let c = PointN::new([3f64, 1f64]);
+        assert!(CubicBezier::new(a, a, b, c).cusp::<f64>() == Some(0.0));
+        assert!(CubicBezier::new(a, b, c, c).cusp::<f64>() == Some(1.0));
+        assert!(CubicBezier::new(a, b, c, PointN::new([4f64, 0f64])).cusp::<f64>().is_none());
+    }
+
+    #[test]
+    fn winding_number() {
+        // a square from (0,0) to (2,2) with bulging sides except for a straight bottom
+        let path = [
+            CubicBezier::new(PointN::new([0f64, 0f64]), PointN::new([0.5f64, 0f64]),
+                             PointN::new([1.5f64, 0f64]), PointN::new([2f64, 0f64])),
+            CubicBezier::new(PointN::new([2f64, 0f64]), PointN::new([2.3f64, 0.5f64]),
+                             PointN::new([2.3f64, 1.5f64]), PointN::new([2f64, 2f64])),
+            CubicBezier::new(PointN::new([2f64, 2f64]), PointN::new([1.5f64, 2.3f64]),
+                             PointN::new([0.5f64, 2.3f64]), PointN::new([0f64, 2f64])),
+            CubicBezier::new(PointN::new([0f64, 2f64]), PointN::new([-0.3f64, 1.5f64]),
+                             PointN::new([-0.3f64, 0.5f64]), PointN::new([0f64, 0f64])),
+        ];
+        let reversed = [path[3].reverse(), path[2].reverse(), path[1].reverse(), path[0].reverse()];
+
+        let inside = [[1f64, 1f64], [2.1f64, 1f64], [1f64, 2.1f64], [-0.1f64, 1f64],
+                      // rays through the corners
+                      [1f64, 2f64], [-0.1f64, 0.5f64]];
+        for p in inside.iter() {
+            assert!(super::winding_number::<_, f64>(&path, PointN::new(*p)) == 1);
+            assert!(super::winding_number::<_, f64>(&reversed, PointN::new(*p)) == -1);
+        }
+        let outside = [[3f64, 1f64], [-1f64, 1f64], [1f64, 3f64], [1f64, -1f64],
+                       // rays through the corners
+                       [-1f64, 2f64], [-1f64, 0f64], [2.5f64, 2f64]];
+        for p in outside.iter() {
+            assert!(super::winding_number::<_, f64>(&path, PointN::new(*p)) == 0);
+            assert!(super::winding_number::<_, f64>(&reversed, PointN::new(*p)) == 0);
+        }
+    }
+
+
+    #[test]
+    fn arclen_f64_equivalence() {
+        // the unit circle approximation of circle_circumference_approximation()
+        let c = 0.551915024494;
+        let quadrants = [
+            CubicBezier::new(PointN::new([0f64, 1f64]), PointN::new([c, 1f64]),
+                             PointN::new([1f64, c]), PointN::new([1f64, 0f64])),
+            CubicBezier::new(PointN::new([1f64, 0f64]), PointN::new([1f64, -c]),
+                             PointN::new([c, -1f64]), PointN::new([0f64, -1f64])),
+            CubicBezier::new(PointN::new([0f64, -1f64]), PointN::new([-c, -1f64]),
+                             PointN::new([-1f64, -c]), PointN::new([-1f64, 0f64])),
+            CubicBezier::new(PointN::new([-1f64, 0f64]), PointN::new([-1f64, c]),
+                             PointN::new([-c, 1f64]), PointN::new([0f64, 1f64])),
+        ];
+        let nsteps = 1e3 as usize;
+        let mut circumference = 0.0;
+        for quadrant in quadrants.iter() {
+            let arclen = quadrant.arclen_f64(nsteps);
+            assert!(arclen == quadrant.arclen::<NativeFloat>(nsteps));
+            circumference += arclen;
+        }
+        assert!((circumference - 2.0 * core::f64::consts::PI).abs() < 1e-2);
+    }
+
+
+    #[test]
+    fn iter_uniform() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
             PointN::new([3.2f64, -4f64]),
         );
+        let nsteps: usize = 10;
+        assert!(bezier.iter_uniform::<f64>(nsteps).count() == nsteps + 1);
+        assert!(bezier.iter_uniform::<f64>(nsteps).next() == Some(bezier.start));
+        assert!(bezier.iter_uniform::<f64>(nsteps).last() == Some(bezier.end));
+        for (i, p) in bezier.iter_uniform::<f64>(nsteps).enumerate() {
+            let t = i as f64 * 1f64/(nsteps as f64);
+            assert!(p.distance(&bezier.eval_casteljau(t)) < EPSILON);
+        }
+        assert!(bezier.iter_uniform::<f64>(0).eq(core::iter::once(bezier.start)));
+    }
 
+
+    #[test]
+    fn from_hermite() {
+        let p0 = PointN::new([0f64, 1.77f64]);
+        let p1 = PointN::new([3.2f64, -4f64]);
+        let m0 = PointN::new([3.3f64, -8.31f64]);
+        let m1 = PointN::new([-3.3f64, -21f64]);
+        let bezier = CubicBezier::from_hermite(p0, p1, m0, m1);
+        assert!(bezier.eval(0.0) == p0);
+        assert!(bezier.eval(1.0).distance(&p1) < EPSILON);
+        let derivative = bezier.derivative::<f64>();
         let max_err = 1e-14;
-        let nsteps: usize =  1000;                                      
+        assert!(derivative.eval(0.0).distance(&m0) < max_err);
+        assert!(derivative.eval(1.0).distance(&m1) < max_err);
+    }
+
+
+    #[test]
+    fn min_distance() {
+        let arch = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 0f64]),
+        );
+        // the closest points are the apex of the arch at (1.5, 1.5) and the bottom of the cup at (1.5, 2.5)
+        let cup = CubicBezier::new(
+            PointN::new([0f64, 4f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, 2f64]),
+            PointN::new([3f64, 4f64]),
+        );
+        let max_err = 1e-9;
+        let (t_arch, t_cup, dist): (f64, f64, f64) = arch.min_distance(&cup);
+        assert!((t_arch - 0.5).abs() < max_err && (t_cup - 0.5).abs() < max_err);
+        assert!((dist - 1.0).abs() < max_err);
+        // the result is symmetric
+        let (t_cup, t_arch, dist): (f64, f64, f64) = cup.min_distance(&arch);
+        assert!((t_arch - 0.5).abs() < max_err && (t_cup - 0.5).abs() < max_err);
+        assert!((dist - 1.0).abs() < max_err);
+
+        // the closest point of a line to the right is the end point of the arch at (3, 0)
+        let line = CubicBezier::new(
+            PointN::new([4f64, -2f64]),
+            PointN::new([4f64, -1f64]),
+            PointN::new([4f64, 1f64]),
+            PointN::new([4f64, 2f64]),
+        );
+        let (t_arch, t_line, dist): (f64, f64, f64) = arch.min_distance(&line);
+        assert!((t_arch - 1.0).abs() < max_err && (t_line - 0.5).abs() < max_err);
+        assert!((dist - 1.0).abs() < max_err);
+    }
+
+
+    #[test]
+    fn bezier_conversion() {
+        let cubic = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let bezier: Bezier<PointN<f64, 2>, 4> = cubic.into();
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
         for t in 0..=nsteps {
             let t = t as f64 * 1f64/(nsteps as f64);
-            let p1 = bezier.eval(t);
-            let p2 = bezier.eval_casteljau(t);
-            let err = p2-p1;
-            //dbg!(p1);
-            //dbg!(p2);
-            for axis in err {
-                assert!(axis.abs() < max_err);
-            }
-            
+            assert!(bezier.eval(t).distance(&cubic.eval_casteljau(t)) < max_err);
         }
+        assert!(CubicBezier::from(bezier) == cubic);
     }
 
+
     #[test]
-    fn split_equivalence() {
-        // chose some arbitrary control points and construct a cubic bezier
-        let bezier = CubicBezier{ 
-                                start:  PointN::new([0f64, 1.77f64]),
-                                ctrl1:  PointN::new([2.9f64, 0f64]),
-                                ctrl2:  PointN::new([4.3f64, 3f64]),
-                                end:    PointN::new([3.2f64, -4f64])
-                            };
-        // split it at an arbitrary point
-        let at = 0.5;
-        let (left, right) = bezier.split(at);
-        // compare left and right subcurves with parent curve
-        // this is tricky as we have to map t->t/2 (for left) which will 
-        // inevitably contain rounding errors from floating point ops.
-        // instead, take the difference of the two points which must not exceed the absolute error
-        // TODO update test to use Point::distance() instead for error
+    fn from_quadratic() {
+        let quadratic = QuadraticBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let cubic: CubicBezier<PointN<f64, 2>> = quadratic.into();
         let max_err = 1e-14;
-        let nsteps: usize =  1000;                                      
+        let nsteps: usize = 100;
         for t in 0..=nsteps {
             let t = t as f64 * 1f64/(nsteps as f64);
-            //dbg!(bezier.eval(t/2.0));
-            //dbg!(left.eval(t));
-            // left
-            let mut err = bezier.eval(t/2.0) - left.eval(t);
-            //dbg!(err);
-            for axis in err {
-                assert!(axis.abs() < max_err);
-            }
-            // right
-            err = bezier.eval((t*0.5)+0.5) - right.eval(t);
-            //dbg!(err);
-            for axis in err {
-                assert!(axis.abs() < max_err);
-            }  
+            assert!(cubic.eval(t).distance(&quadratic.eval(t)) < max_err);
         }
+        // the conversion is lossless
+        let (reduced, err): (QuadraticBezier<PointN<f64, 2>>, f64) = cubic.reduce();
+        assert!(err < max_err);
+        assert!(reduced.ctrl.distance(&quadratic.ctrl) < max_err);
     }
 
 
     #[test]
-    fn bounding_box_contains() {
-        // check if bounding box for a curve contains all points (with some approximation error)
-        let bezier = CubicBezier{ 
-                        start:  PointN::new([0f64, 1.77f64]),
-                        ctrl1: PointN::new([2.9f64, 0f64]),
-                        ctrl2: PointN::new([4.3f64, -3f64]),
-                        end:   PointN::new([3.2f64, 4f64])
-        };
-
-        let bounds = bezier.bounding_box::<f64>();
-
-        let max_err = 1e-2;
-
-        let nsteps: usize =  100;                                      
-        for t in 0..=nsteps {
-            let t = t as f64 * 1f64/(nsteps as f64);
-            let p = bezier.eval_casteljau(t);
-            //dbg!(t);
-            //dbg!(p);
-            //dbg!(xmin-max_err, ymin-max_err, xmax+max_err, ymax+max_err);
-            for (idx, axis) in p.into_iter().enumerate() {
-                assert!( (axis >= (bounds[idx].0 - max_err)) && (axis <= (bounds[idx].1 + max_err)) )
-            }
+    fn solve_t_for_axis_three_roots() {
+        // y crosses zero three times, which takes the trigonometric branch of real_roots()
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, -1f64]),
+            PointN::new([1f64, 4f64]),
+            PointN::new([2f64, -4f64]),
+            PointN::new([3f64, 1f64]),
+        );
+        let roots = bezier.solve_t_for_axis(0f64, 1);
+        assert!(roots.len() == 3);
+        for t in roots.iter() {
+            assert!(bezier.eval_casteljau(*t).axis(1).abs() < 1e-12);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,257 @@
+//! The Curve trait is a common interface of the parametric curves over t in [0,1],
+//! so that algorithms can be written once for any of them.
+//! The methods take and return the native float type, unlike the generic methods of the curves,
+//! which keeps the trait object safe (e.g. `&dyn Curve<P>`) as long as split() isn't needed.
+
+use super::*;
+use super::point::Point;
+use super::line::LineSegment;
+use super::quadratic_bezier::QuadraticBezier;
+use super::cubic_bezier::CubicBezier;
+use super::bezier::Bezier;
+
+pub trait Curve<P>
+where
+P: Point<Scalar = NativeFloat>,
+{
+    /// Returns the point of the curve at t = 0
+    fn start(&self) -> P;
+
+    /// Returns the point of the curve at t = 1
+    fn end(&self) -> P;
+
+    /// Evaluates the curve at t, which should be in [0,1]
+    fn eval(&self, t: NativeFloat) -> P;
+
+    /// Evaluates the first derivative of the curve at t (the unnormalized tangent vector)
+    fn derivative_at(&self, t: NativeFloat) -> P;
+
+    /// Splits the curve at t into two curves of the same type covering [0, t] and [t, 1]
+    fn split(&self, t: NativeFloat) -> (Self, Self)
+    where
+    Self: Sized;
+
+    /// Returns the bounding box of the curve as an array of (min, max) tuples for each dimension (its index)
+    fn bounding_box(&self) -> [(NativeFloat, NativeFloat); P::DIM];
+}
+
+impl<P> Curve<P> for LineSegment<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn start(&self) -> P {
+        self.start
+    }
+
+    fn end(&self) -> P {
+        self.end
+    }
+
+    fn eval(&self, t: NativeFloat) -> P {
+        LineSegment::eval(self, t)
+    }
+
+    fn derivative_at(&self, _t: NativeFloat) -> P {
+        LineSegment::derivative::<NativeFloat>(self)
+    }
+
+    fn split(&self, t: NativeFloat) -> (Self, Self) {
+        LineSegment::split(self, t)
+    }
+
+    fn bounding_box(&self) -> [(NativeFloat, NativeFloat); P::DIM] {
+        LineSegment::bounding_box(self)
+    }
+}
+
+impl<P> Curve<P> for QuadraticBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn start(&self) -> P {
+        self.start
+    }
+
+    fn end(&self) -> P {
+        self.end
+    }
+
+    fn eval(&self, t: NativeFloat) -> P {
+        QuadraticBezier::eval(self, t)
+    }
+
+    fn derivative_at(&self, t: NativeFloat) -> P {
+        self.derivative::<NativeFloat>().eval(t)
+    }
+
+    fn split(&self, t: NativeFloat) -> (Self, Self) {
+        QuadraticBezier::split(self, t)
+    }
+
+    fn bounding_box(&self) -> [(NativeFloat, NativeFloat); P::DIM] {
+        QuadraticBezier::bounding_box(self)
+    }
+}
+
+impl<P> Curve<P> for CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn start(&self) -> P {
+        self.start
+    }
+
+    fn end(&self) -> P {
+        self.end
+    }
+
+    fn eval(&self, t: NativeFloat) -> P {
+        CubicBezier::eval(self, t)
+    }
+
+    fn derivative_at(&self, t: NativeFloat) -> P {
+        self.derivative::<NativeFloat>().eval(t)
+    }
+
+    fn split(&self, t: NativeFloat) -> (Self, Self) {
+        CubicBezier::split(self, t)
+    }
+
+    fn bounding_box(&self) -> [(NativeFloat, NativeFloat); P::DIM] {
+        CubicBezier::bounding_box(self)
+    }
+}
+
+impl<P, const N: usize> Curve<P> for Bezier<P, {N}>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn start(&self) -> P {
+        self.control_points[0]
+    }
+
+    fn end(&self) -> P {
+        self.control_points[N-1]
+    }
+
+    fn eval(&self, t: NativeFloat) -> P {
+        Bezier::eval(self, t)
+    }
+
+    /// Evaluates the derivative with De Casteljau's algorithm over the differences of the control points,
+    /// so that (unlike Bezier::derivative()) no bound on N is needed. The derivative of a point (N = 1) is zero.
+    fn derivative_at(&self, t: NativeFloat) -> P {
+        if N < 2 {
+            return P::default()
+        }
+        let mut p: [P; N] = self.control_points;
+        for i in 0..N-1 {
+            p[i] = (p[i+1] - p[i]) * ((N-1) as NativeFloat);
+        }
+        for i in 1..N-1 {
+            for j in 0..N-1 - i {
+                p[j] = p[j] * (1.0 - t) + p[j+1] * t;
+            }
+        }
+        p[0]
+    }
+
+    fn split(&self, t: NativeFloat) -> (Self, Self) {
+        Bezier::split(self, t)
+    }
+
+    fn bounding_box(&self) -> [(NativeFloat, NativeFloat); P::DIM] {
+        Bezier::bounding_box(self)
+    }
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+    use super::point_generic::PointN;
+
+    /// Samples the curve and checks that every sample is inside of its bounding box.
+    /// Returns the largest distance of a sample from the chord
+    fn check_samples<C>(curve: &C) -> f64
+    where
+    C: Curve<PointN<f64, 2>>,
+    {
+        let bounds = curve.bounding_box();
+        let chord = LineSegment::new(curve.start(), curve.end());
+        let mut max_dist = 0f64;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = curve.eval(t);
+            for dim in 0..2 {
+                assert!(p.axis(dim) >= bounds[dim].0 - 1e-12 && p.axis(dim) <= bounds[dim].1 + 1e-12);
+            }
+            max_dist = max_dist.max(chord.distance_to_point::<f64>(p));
+        }
+        max_dist
+    }
+
+    /// Checks the derivative against a central difference quotient and split() against eval()
+    fn check_derivative_and_split<C>(curve: &C)
+    where
+    C: Curve<PointN<f64, 2>>,
+    {
+        let h = 1e-6;
+        let max_err = 1e-6;
+        for t in [0.1, 0.3, 0.5, 0.8].iter() {
+            let quotient = (curve.eval(t + h) - curve.eval(t - h)) * (0.5 / h);
+            assert!(quotient.distance(&curve.derivative_at(*t)) < max_err);
+        }
+        let (left, right) = curve.split(0.3);
+        assert!(left.end().distance(&curve.eval(0.3)) < 1e-12);
+        assert!(left.eval(0.5).distance(&curve.eval(0.15)) < 1e-12);
+        assert!(right.eval(0.5).distance(&curve.eval(0.65)) < 1e-12);
+    }
+
+    #[test]
+    fn generic_over_curves() {
+        let quadratic = QuadraticBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([2f64, 0f64]),
+        );
+        let cubic = CubicBezier::from(quadratic);
+        let bezier: Bezier<PointN<f64, 2>, 3> = Bezier::new([quadratic.start, quadratic.ctrl, quadratic.end]);
+        let line = LineSegment::new(quadratic.start, quadratic.end);
+
+        // the same parabola with its apex at (1,1)
+        assert!((check_samples(&quadratic) - 1.0).abs() < 1e-12);
+        assert!((check_samples(&cubic) - 1.0).abs() < 1e-12);
+        assert!((check_samples(&bezier) - 1.0).abs() < 1e-12);
+        assert!(check_samples(&line) < 1e-12);
+        check_derivative_and_split(&quadratic);
+        check_derivative_and_split(&cubic);
+        check_derivative_and_split(&bezier);
+        check_derivative_and_split(&line);
+
+        // trait objects of different curve types
+        let curves: [&dyn Curve<PointN<f64, 2>>; 3] = [&quadratic, &cubic, &bezier];
+        for curve in curves.iter() {
+            assert!(curve.derivative_at(0.0).distance(&PointN::new([2f64, 4f64])) < 1e-12);
+            assert!((curve.bounding_box()[1].1 - 1.0).abs() < 1e-12);
+        }
+    }
+}
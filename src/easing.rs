@@ -0,0 +1,177 @@
+//! Easing (timing) functions for animations as defined by CSS cubic-bezier(x1, y1, x2, y2):
+//! a 2D cubic bezier curve from (0,0) to (1,1) which maps the progress in time (x)
+//! to the progress of the animation (y).
+
+use super::*;
+use super::cubic_bezier::CubicBezier;
+use super::point::Point;
+use super::point_generic::PointN;
+
+/// Number of samples of x at equally spaced parameters, see Easing::sample()
+const EASING_SAMPLES: usize = 11;
+/// Maximum number of Newton or bisection steps to find the parameter at x, see Easing::sample()
+/// (bisection alone shrinks the bracket below EASING_TOLERANCE within these)
+const EASING_MAX_STEPS: usize = 64;
+/// Width of the bracket around the parameter at x (or size of a Newton step) at which the search stops
+const EASING_TOLERANCE: NativeFloat = 4.0 * EPSILON;
+
+/// A timing function given by a cubic bezier curve from (0,0) to (1,1) which is monotone in x,
+/// so that there is exactly one y for each x in [0,1].
+/// The x coordinates of the curve at equally spaced parameters are sampled on construction
+/// to quickly find a starting point for the parameter at a given x.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Easing {
+    curve: CubicBezier<PointN<NativeFloat, 2>>,
+    samples: [NativeFloat; EASING_SAMPLES],
+}
+
+impl Easing {
+
+    /// Creates the timing function with the inner control points (x1, y1) and (x2, y2).
+    /// Returns None if x1 or x2 are outside of [0,1] (like CSS does),
+    /// which guarantees that the curve is monotone in x.
+    /// There is only this one reason for an invalid curve, so like the other fallible constructors
+    /// of the crate (e.g. BSpline::new()) it returns an Option instead of an error type.
+    pub fn new(x1: NativeFloat, y1: NativeFloat, x2: NativeFloat, y2: NativeFloat) -> Option<Self> {
+        if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+            return None
+        }
+        let curve = CubicBezier::new(
+            PointN::new([0.0, 0.0]),
+            PointN::new([x1, y1]),
+            PointN::new([x2, y2]),
+            PointN::new([1.0, 1.0]),
+        );
+        let mut samples = [0.0; EASING_SAMPLES];
+        for i in 0..EASING_SAMPLES {
+            let t = i as NativeFloat / (EASING_SAMPLES - 1) as NativeFloat;
+            samples[i] = curve.eval_casteljau(t).axis(0);
+        }
+        Some(Easing { curve, samples })
+    }
+
+    pub fn linear() -> Self {
+        Easing::new(0.0, 0.0, 1.0, 1.0).unwrap()
+    }
+
+    pub fn ease() -> Self {
+        Easing::new(0.25, 0.1, 0.25, 1.0).unwrap()
+    }
+
+    pub fn ease_in() -> Self {
+        Easing::new(0.42, 0.0, 1.0, 1.0).unwrap()
+    }
+
+    pub fn ease_out() -> Self {
+        Easing::new(0.0, 0.0, 0.58, 1.0).unwrap()
+    }
+
+    pub fn ease_in_out() -> Self {
+        Easing::new(0.42, 0.0, 0.58, 1.0).unwrap()
+    }
+
+    /// Returns the underlying curve
+    pub fn curve(&self) -> &CubicBezier<PointN<NativeFloat, 2>> {
+        &self.curve
+    }
+
+    /// Returns the progress of the animation y at the progress in time x, which is clamped to [0,1].
+    /// The parameter at x is bracketed by the two enclosing samples and searched with a safeguarded Newton iteration:
+    /// Newton steps are taken as long as they stay inside the bracket, otherwise the bracket is bisected.
+    /// The bracket is narrowed after each step, so the search converges even where the curve is (nearly)
+    /// vertical in x (e.g. at the end points for x1 = 0 or x2 = 1). It stops once the bracket or the step
+    /// is smaller than EASING_TOLERANCE.
+    pub fn sample(&self, x: NativeFloat) -> NativeFloat {
+        let x = x.clamp(0.0, 1.0);
+        let step = 1.0 / (EASING_SAMPLES - 1) as NativeFloat;
+        // the samples are non-decreasing as the curve is monotone in x
+        let i = self.samples[1..EASING_SAMPLES - 1].iter().take_while(|s| **s <= x).count();
+        let (x0, x1) = (self.samples[i], self.samples[i + 1]);
+        let (mut t0, mut t1) = (i as NativeFloat * step, (i + 1) as NativeFloat * step);
+        let mut t = if x1 - x0 < EPSILON { t0 } else { t0 + (x - x0) / (x1 - x0) * step };
+
+        let derivative = self.curve.derivative::<NativeFloat>();
+        for _ in 0..EASING_MAX_STEPS {
+            let error = self.curve.eval_casteljau(t).axis(0) - x;
+            if error == 0.0 {
+                break;
+            }
+            // keep the bracket around the parameter
+            if error > 0.0 { t1 = t; } else { t0 = t; }
+            let slope = derivative.eval(t).axis(0);
+            let newton = t - error / slope;
+            let t_next = if newton > t0 && newton < t1 { newton } else { (t0 + t1) * 0.5 };
+            let converged = (t_next - t).abs() < EASING_TOLERANCE || t1 - t0 < EASING_TOLERANCE;
+            t = t_next;
+            if converged {
+                break;
+            }
+        }
+        return self.curve.eval_casteljau(t).axis(1)
+    }
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn linear() {
+        let linear = Easing::linear();
+        assert!((linear.sample(0.3) - 0.3).abs() < 1e-9);
+        for x in 0..=100 {
+            let x = x as f64 * 1f64/100f64;
+            assert!((linear.sample(x) - x).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn sample_matches_y_for_x() {
+        for easing in [Easing::ease(), Easing::ease_in(), Easing::ease_out(), Easing::ease_in_out()].iter() {
+            assert!(easing.sample(0.0) == 0.0 && easing.sample(1.0) == 1.0);
+            // clamped outside of [0,1]
+            assert!(easing.sample(-0.5) == 0.0 && easing.sample(1.5) == 1.0);
+            let mut prev = 0.0;
+            for x in 1..100 {
+                let x = x as f64 * 1f64/100f64;
+                let y = easing.sample(x);
+                assert!((y - easing.curve().y_for_x(x).unwrap()).abs() < 1e-9);
+                assert!(y >= prev);
+                prev = y;
+            }
+        }
+        // ease-in-out is symmetric around (0.5, 0.5)
+        let ease_in_out = Easing::ease_in_out();
+        assert!((ease_in_out.sample(0.5) - 0.5).abs() < 1e-9);
+        assert!((ease_in_out.sample(0.2) + ease_in_out.sample(0.8) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn vertical_end_points() {
+        // with x1 = 0 and x2 = 1 the curve is vertical in x at both end points,
+        // so the parameter changes fastest there
+        for easing in [Easing::new(0.0, 1.0, 1.0, 0.0).unwrap(), Easing::new(0.0, 0.0, 1.0, 1.0).unwrap(),
+                       Easing::new(0.0, 0.3, 1.0, 0.6).unwrap()].iter() {
+            let max_err = 1e-9;
+            for &x in [1e-9, 1e-6, 1e-4, 1e-2, 0.5, 0.99, 0.9999, 1.0 - 1e-6, 1.0 - 1e-9].iter() {
+                assert!((easing.sample(x) - easing.curve().y_for_x(x).unwrap()).abs() < max_err);
+            }
+            for x in 0..=10000 {
+                let x = x as f64 * 1f64/10000f64;
+                assert!((easing.sample(x) - easing.curve().y_for_x(x).unwrap()).abs() < max_err);
+            }
+        }
+    }
+
+    #[test]
+    fn overshoot_and_validation() {
+        // y may leave [0,1] for "back" easing, x may not
+        let back = Easing::new(0.68, -0.6, 0.32, 1.6).unwrap();
+        assert!(back.sample(0.1) < 0.0 && back.sample(0.9) > 1.0);
+        assert!(Easing::new(-0.1, 0.0, 0.5, 1.0).is_none());
+        assert!(Easing::new(0.5, 0.0, 1.1, 1.0).is_none());
+        assert!(Easing::new(NativeFloat::NAN, 0.0, 0.5, 1.0).is_none());
+    }
+}
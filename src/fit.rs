@@ -0,0 +1,384 @@
+//! Fitting of cubic bezier curves to sampled points, e.g. from digitized pen input.
+//! The least squares fit follows "An Algorithm for Automatically Fitting Digitized Curves"
+//! (Philip J. Schneider, Graphics Gems, 1990) but solves for both inner control points
+//! without constraining the tangent directions at the end points.
+//! smooth_path() interpolates waypoints instead, with handles derived from the neighbouring points.
+
+use super::*;
+use super::point::Point;
+use super::cubic_bezier::CubicBezier;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Maximum number of reparameterizations to bring the fit within tolerance
+const FIT_MAX_ITERATIONS: usize = 16;
+/// Newton iterations per point and reparameterization
+const FIT_NEWTON_STEPS: usize = 3;
+
+
+/// Fits a single cubic bezier curve to the points with least squares.
+/// The curve starts and ends at the first and last point, the inner points are
+/// parameterized by their chord length. If the fit is not within tolerance of all points,
+/// the parameters are refined by Newton iterations on the fitted curve and the fit is repeated.
+/// The best fit is returned, even if it exceeds tolerance.
+/// Panics if points is empty.
+pub fn fit_cubic<P, F>(points: &[P], tolerance: F) -> CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    fit_cubic_with_error(points, tolerance.into()).0
+}
+
+
+/// Fits a sequence of connected cubic bezier curves to the points (Schneider's algorithm).
+/// A single curve is fitted with fit_cubic(); if it is not within tolerance of all points,
+/// the points are split at the one with the largest error and both halves are fitted recursively.
+/// The curves are joined at sample points, but the tangents at the joints are not constrained
+/// to be continuous. Panics if points is empty.
+#[cfg(feature = "alloc")]
+pub fn fit_path<P, F>(points: &[P], tolerance: F) -> Vec<CubicBezier<P>>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    let mut path = Vec::new();
+    fit_path_recursive(points, tolerance.into(), &mut path);
+    path
+}
+
+/// Returns a C1 continuous path of cubic bezier curves through the waypoints, one curve between each two
+/// consecutive points (like the "smooth" tool of drawing applications).
+/// The handles at an interior point p[i] are parallel to the line through its neighbours:
+/// ctrl = p[i] ± (p[i+1] - p[i-1]) * smoothness / 6, so the incoming and outgoing handles have the same length.
+/// A smoothness of 1 yields the (uniform) Catmull-Rom spline through the points, 0 yields the polyline.
+/// The end points get one-sided handles towards their only neighbour: p[0] + (p[1] - p[0]) * smoothness / 3,
+/// which is the Catmull-Rom handle for a mirrored neighbour. Returns an empty path for less than two points.
+#[cfg(feature = "alloc")]
+pub fn smooth_path<P, F>(points: &[P], smoothness: F) -> Vec<CubicBezier<P>>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    let smoothness = smoothness.into();
+    let n = points.len();
+    if n < 2 {
+        return Vec::new()
+    }
+    // the handle vector at each point, which is subtracted for the incoming and added for the outgoing handle
+    let handle = |i: usize| -> P {
+        if i == 0 {
+            (points[1] - points[0]) * (smoothness / 3.0)
+        } else if i == n - 1 {
+            (points[n - 1] - points[n - 2]) * (smoothness / 3.0)
+        } else {
+            (points[i + 1] - points[i - 1]) * (smoothness / 6.0)
+        }
+    };
+    let mut path = Vec::with_capacity(n - 1);
+    for i in 0..n - 1 {
+        path.push(CubicBezier::new(points[i], points[i] + handle(i), points[i + 1] - handle(i + 1), points[i + 1]));
+    }
+    path
+}
+
+#[cfg(feature = "alloc")]
+fn fit_path_recursive<P>(points: &[P], tolerance: NativeFloat, path: &mut Vec<CubicBezier<P>>)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let (curve, error, index) = fit_cubic_with_error(points, tolerance);
+    // the end points are always interpolated, so they can't be the point of maximum error
+    if error <= tolerance || index == 0 || index == points.len() - 1 {
+        path.push(curve);
+        return;
+    }
+    fit_path_recursive(&points[..=index], tolerance, path);
+    fit_path_recursive(&points[index..], tolerance, path);
+}
+
+
+/// Like fit_cubic(), but also returns the maximum distance of a point to the curve at its parameter
+/// together with the index of that point.
+pub(crate) fn fit_cubic_with_error<P>(points: &[P], tolerance: NativeFloat) -> (CubicBezier<P>, NativeFloat, usize)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    assert!(!points.is_empty());
+    let mut curve = least_squares(points, chord_lengths(points));
+    let (mut error, mut index) = max_error(&curve, points);
+    for _ in 0..FIT_MAX_ITERATIONS {
+        if error <= tolerance {
+            break;
+        }
+        let params = chord_lengths(points)
+                        .zip(points.iter())
+                        .map(|(t, p)| reparameterize(&curve, *p, t));
+        let candidate = least_squares(points, params);
+        let (candidate_error, candidate_index) = max_error(&candidate, points);
+        if candidate_error >= error {
+            break;
+        }
+        curve = candidate;
+        error = candidate_error;
+        index = candidate_index;
+    }
+    (curve, error, index)
+}
+
+
+/// Returns the normalized cumulative chord length of each point as its parameter in [0,1].
+/// Falls back to uniform parameters if all points coincide.
+fn chord_lengths<'a, P>(points: &'a [P]) -> impl Iterator<Item = NativeFloat> + 'a
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Sub<P, Output = P>,
+{
+    let total: NativeFloat = points.windows(2).map(|w| w[0].distance(&w[1])).sum();
+    let n = points.len();
+    let mut length = 0.0;
+    points.iter().enumerate().map(move |(i, p)| {
+        if i > 0 {
+            length += points[i - 1].distance(p);
+        }
+        if total < EPSILON {
+            if n > 1 { i as NativeFloat / (n - 1) as NativeFloat } else { 0.0 }
+        } else {
+            length / total
+        }
+    })
+}
+
+
+/// Refines the parameter t of point p on the curve with Newton iterations
+/// on the derivative of the squared distance
+fn reparameterize<P>(curve: &CubicBezier<P>, p: P, t: NativeFloat) -> NativeFloat
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let derivative = curve.derivative::<NativeFloat>();
+    let second_derivative = derivative.derivative::<NativeFloat>();
+    let mut t = t;
+    for _ in 0..FIT_NEWTON_STEPS {
+        let diff = curve.eval_casteljau(t) - p;
+        let d1 = derivative.eval_casteljau(t);
+        let d2 = second_derivative.eval(t);
+        let denominator = d1.dot(&d1) + diff.dot(&d2);
+        if denominator.abs() < EPSILON {
+            break;
+        }
+        t = (t - diff.dot(&d1) / denominator).clamp(0.0, 1.0);
+    }
+    t
+}
+
+
+/// Solves for the inner control points minimizing the sum of squared distances
+/// between the points and the curve evaluated at their parameters, with fixed end points.
+/// With the bernstein polynomials b0..b3 and r_i = p_i - b0(t_i) * start - b3(t_i) * end,
+/// the normal equations are
+///     | sum(b1*b1) sum(b1*b2) | | ctrl1 |   | sum(b1 * r_i) |
+///     | sum(b1*b2) sum(b2*b2) | | ctrl2 | = | sum(b2 * r_i) |
+/// If they are singular (e.g. for less than four points), the control points
+/// are placed on the line between the end points.
+fn least_squares<P>(points: &[P], params: impl Iterator<Item = NativeFloat>) -> CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let start = points[0];
+    let end = points[points.len() - 1];
+
+    let (mut a11, mut a12, mut a22) = (0.0, 0.0, 0.0);
+    let mut rhs1 = P::default();
+    let mut rhs2 = P::default();
+    for (p, t) in points.iter().zip(params) {
+        let mt = 1.0 - t;
+        let b0 = mt * mt * mt;
+        let b1 = 3.0 * mt * mt * t;
+        let b2 = 3.0 * mt * t * t;
+        let b3 = t * t * t;
+        a11 += b1 * b1;
+        a12 += b1 * b2;
+        a22 += b2 * b2;
+        let r = *p - (start * b0 + end * b3);
+        rhs1 = rhs1 + r * b1;
+        rhs2 = rhs2 + r * b2;
+    }
+
+    let determinant = a11 * a22 - a12 * a12;
+    if determinant.abs() < EPSILON {
+        let third = (end - start) * (1.0 / 3.0);
+        return CubicBezier::new(start, start + third, end - third, end);
+    }
+    // cramer's rule
+    let ctrl1 = (rhs1 * a22 - rhs2 * a12) * (1.0 / determinant);
+    let ctrl2 = (rhs2 * a11 - rhs1 * a12) * (1.0 / determinant);
+    CubicBezier::new(start, ctrl1, ctrl2, end)
+}
+
+
+/// Returns the maximum distance between a point and its closest point on the curve
+/// (found by refining its chord length parameter) and the index of that point
+fn max_error<P>(curve: &CubicBezier<P>, points: &[P]) -> (NativeFloat, usize)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let mut result = (0.0, 0);
+    for (i, (t, p)) in chord_lengths(points).zip(points.iter()).enumerate() {
+        let t = reparameterize(curve, *p, t);
+        let error = curve.eval_casteljau(t).distance(p);
+        if error > result.0 {
+            result = (error, i);
+        }
+    }
+    result
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+    use super::point_generic::PointN;
+
+    #[test]
+    fn fit_cubic_recovers_curve() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([3f64, 2.5f64]),
+            PointN::new([4f64, 0f64]),
+        );
+        let nsteps: usize = 50;
+        let mut points = [PointN::new([0f64; 2]); 51];
+        for t in 0..=nsteps {
+            points[t] = bezier.eval(t as f64 * 1f64/(nsteps as f64));
+        }
+        let tolerance = 5e-3;
+        let fit = fit_cubic(&points, tolerance);
+        assert!(fit.start == bezier.start && fit.end == bezier.end);
+        let (_, error, _) = fit_cubic_with_error(&points, tolerance);
+        assert!(error <= tolerance);
+        assert!(fit.ctrl1.distance(&bezier.ctrl1) < 0.15);
+        assert!(fit.ctrl2.distance(&bezier.ctrl2) < 0.15);
+        // the initial chord length parameterization is refined
+        let (_, unrefined_error, _) = fit_cubic_with_error(&points, 1.0);
+        assert!(unrefined_error > error);
+    }
+
+    #[test]
+    fn fit_cubic_degenerate() {
+        // too few points to determine the control points
+        let points = [PointN::new([0f64, 0f64]), PointN::new([3f64, 3f64])];
+        let fit = fit_cubic(&points, 1e-4);
+        assert!(fit.ctrl1.distance(&PointN::new([1f64, 1f64])) < EPSILON);
+        assert!(fit.ctrl2.distance(&PointN::new([2f64, 2f64])) < EPSILON);
+        let points = [PointN::new([1f64, 2f64])];
+        let fit = fit_cubic(&points, 1e-4);
+        assert!(fit.start == points[0] && fit.end == points[0]);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fit_path_figure_eight() {
+        // lemniscate of gerono
+        let nsteps: usize = 200;
+        let mut points = [PointN::new([0f64; 2]); 201];
+        for i in 0..=nsteps {
+            let t = i as f64 * 2.0 * core::f64::consts::PI / (nsteps as f64);
+            points[i] = PointN::new([t.cos(), t.sin() * t.cos()]);
+        }
+        let tolerance = 1e-3;
+        let path = fit_path(&points, tolerance);
+        assert!(path.len() > 1);
+        assert!(path[0].start == points[0] && path[path.len() - 1].end == points[nsteps]);
+        for pair in path.windows(2) {
+            assert!(pair[0].end == pair[1].start);
+        }
+        for p in points.iter() {
+            let min_dist = path.iter()
+                            .map(|segment| segment.project::<f64>(*p).1)
+                            .fold(f64::INFINITY, |a, b| a.min(b));
+            assert!(min_dist <= tolerance);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn smooth_path() {
+        use super::cubic_bezier::{is_g1_continuous, is_c1_continuous};
+        let points = [
+            PointN::new([0f64, 0f64]),
+            PointN::new([1f64, 2f64]),
+            PointN::new([3f64, 2.5f64]),
+            PointN::new([4f64, 0f64]),
+            PointN::new([6f64, -1f64]),
+        ];
+        for &smoothness in [0.5, 1.0, 1.5].iter() {
+            let path = super::smooth_path(&points, smoothness);
+            assert!(path.len() == points.len() - 1);
+            // the path passes through every waypoint
+            for (i, curve) in path.iter().enumerate() {
+                assert!(curve.start == points[i] && curve.end == points[i + 1]);
+            }
+            for w in path.windows(2) {
+                assert!(is_g1_continuous(&w[0], &w[1], 1e-12));
+                assert!(is_c1_continuous(&w[0], &w[1], 1e-12));
+            }
+        }
+        // the catmull-rom spline has the tangent (p[i+1] - p[i-1]) / 2 at the waypoints
+        let path = super::smooth_path(&points, 1.0);
+        let tangent = path[1].derivative::<f64>().eval(0.0);
+        assert!(tangent.distance(&((points[2] - points[0]) * 0.5)) < 1e-12);
+
+        // no smoothing yields the polyline
+        for curve in super::smooth_path(&points, 0.0).iter() {
+            assert!(curve.is_linear(1e-12));
+        }
+        // evenly spaced waypoints on a line yield the uniformly parameterized line
+        let line = [PointN::new([0f64, 0f64]), PointN::new([1f64, 1f64]), PointN::new([2f64, 2f64])];
+        for curve in super::smooth_path(&line, 1.0).iter() {
+            assert!(curve.eval(0.25).distance(&curve.start.interpolate(&curve.end, 0.25)) < 1e-12);
+        }
+        assert!(super::smooth_path(&points[..1], 1.0).is_empty());
+        assert!(super::smooth_path::<PointN<f64, 2>, f64>(&[], 1.0).is_empty());
+    }
+}
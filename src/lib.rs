@@ -15,31 +15,111 @@ use core::ops::{Add, Sub, Mul};
 extern crate num_traits;
 use num_traits::{float::Float};
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 extern crate tinyvec;
 use tinyvec::ArrayVec;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
 pub mod point;
 pub mod point_generic;
+pub mod affine;
 pub mod line;
 pub mod bezier;
 pub mod quadratic_bezier;
 pub mod cubic_bezier;
 pub mod bezier_segment;
-//pub mod rational_bezier;
+pub mod curve;
+pub mod rational_bezier;
+#[cfg(feature = "alloc")]
+pub mod arc;
+#[cfg(feature = "alloc")]
+pub mod outline;
 pub mod bspline;
+pub mod easing;
+pub mod fit;
+pub mod roots;
+pub mod svg;
+#[cfg(feature = "serde")]
+mod serde_array;
 
 
-// Conditionally compiled newtype pattern used to determine which size float to use
-// so that the library can abstract over both 32 and 64 bit architectures (maybe even 16 bit)
-// TODO there has to be a better architectural solution to this...
-//   Option 1.: Is is possible to determine float size at build time?
-//   Option 2.: Is it possible to make this value a 'must-config' in cargo.toml?
-#[cfg(target_pointer_width = "64")]
+// The float type used for the coordinates of points (see the Point trait) and for all internal computations
+// is selected with the mutually exclusive features 'f64' (default) and 'f32'.
+// EPSILON is the machine epsilon of the selected type.
+#[cfg(all(feature = "f32", feature = "f64"))]
+compile_error!("the features 'f32' and 'f64' are mutually exclusive, use default-features = false to select 'f32'");
+
+#[cfg(not(feature = "f32"))]
 type NativeFloat = f64;
-#[cfg(target_pointer_width = "64")]
+#[cfg(not(feature = "f32"))]
 const EPSILON: f64 = f64::EPSILON;
-// same thing for 32 bit
-#[cfg(target_pointer_width = "32")]
+
+#[cfg(feature = "f32")]
 type NativeFloat = f32;
-#[cfg(target_pointer_width = "32")]
-const EPSILON: f32 = f32::EPSILON;
\ No newline at end of file
+#[cfg(feature = "f32")]
+const EPSILON: f32 = f32::EPSILON;
+
+
+// The tests of the modules use f64 coordinates and are only built in the default configuration,
+// this test runs with either float type.
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::point::Point;
+    use super::point_generic::PointN;
+    use super::bezier::Bezier;
+    use super::cubic_bezier::CubicBezier;
+
+    #[test]
+    fn native_float_eval() {
+        let points: [PointN<NativeFloat, 2>; 4] = [
+            PointN::new([0.0, 1.77]),
+            PointN::new([1.1, -1.0]),
+            PointN::new([4.3, 3.0]),
+            PointN::new([3.2, -4.0]),
+        ];
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        let bezier = Bezier::new(points);
+        // scale the tolerance with the precision of the float type
+        let max_err = 1e3 * EPSILON;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as NativeFloat / nsteps as NativeFloat;
+            let p = cubic.eval(t);
+            assert!(p.distance(&cubic.eval_casteljau(t)) < max_err);
+            assert!(p.distance(&bezier.eval(t)) < max_err);
+        }
+        assert!(cubic.eval(0.0 as NativeFloat) == points[0]);
+        assert!(cubic.eval(1.0 as NativeFloat).distance(&points[3]) < max_err);
+    }
+
+    // eval_batch_f32() evaluates the same expression as eval() on f32 lanes
+    #[cfg(feature = "f32")]
+    #[test]
+    fn eval_batch_f32_exact() {
+        let cubic = CubicBezier::new(
+            PointN::new([0.0, 1.77]),
+            PointN::new([1.1, -1.0]),
+            PointN::new([4.3, 3.0]),
+            PointN::new([3.2, -4.0]),
+        );
+        let mut ts = [0f32; 1000];
+        for (i, t) in ts.iter_mut().enumerate() {
+            *t = i as f32 / 999.0;
+        }
+        let mut xs = [0f32; 1000];
+        let mut ys = [0f32; 1000];
+        cubic.eval_batch_f32(&ts, &mut xs, &mut ys);
+        for i in 0..ts.len() {
+            let p = cubic.eval(ts[i]);
+            assert!(xs[i] == p.axis(0) && ys[i] == p.axis(1));
+        }
+    }
+}
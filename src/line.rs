@@ -1,5 +1,6 @@
 use super::*;
 use super::point::Point;
+use super::affine::Affine2;
 
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -103,6 +104,7 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
 
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LineSegment<P>
 {
     pub(crate) start:  P,
@@ -127,6 +129,11 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
         }
     }
 
+    /// Returns the segment transformed by m by applying it to both end points
+    pub fn transform(&self, m: &Affine2) -> Self {
+        LineSegment::new(m.apply(self.start), m.apply(self.end))
+    }
+
     pub fn eval<F>(&self, t: F) -> P 
     where 
     F: Float,
@@ -145,7 +152,7 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
     //     + Mul<F, Output = F>,
     {
         // compute the split point by interpolation
-        let ctrl_ab = self.start + (self.start - self.end) * t;
+        let ctrl_ab = self.start + (self.end - self.start) * t;
 
         return (
             LineSegment {
@@ -162,8 +169,39 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
     pub fn to_line(&self) -> Line<P> {
         Line {
             origin: self.start,
-            vector: self.end - self.end,
+            vector: self.end - self.start,
+        }
+    }
+
+    /// Returns the length of the segment
+    pub fn length<F>(&self) -> F
+    where
+    F: Float,
+    NativeFloat: Into<F>,
+    {
+        return self.start.distance(&self.end).into()
+    }
+
+    /// Returns the intersection point of two 2D segments (including their end points)
+    /// or None if they don't intersect. Parallel segments are never considered intersecting,
+    /// even if they overlap, as there is no single intersection point.
+    /// Only the first two axes of the points are used.
+    pub fn intersection(&self, other: &LineSegment<P>) -> Option<P> {
+        debug_assert!(P::DIM == 2);
+        // solve start + r * t = other.start + s * u for t and u
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let denominator = r.cross_2d(&s);
+        if denominator.abs() < EPSILON {
+            return None;
         }
+        let diff = other.start - self.start;
+        let t = diff.cross_2d(&s) / denominator;
+        let u = diff.cross_2d(&r) / denominator;
+        if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        return Some(self.start + r * t)
     }
 
     /// Return the distance from the LineSegment to Point p by calculating the projection
@@ -175,26 +213,34 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
         let l2 = (self.end - self.start).squared_length();
         // if start and endpoint are approx the same, return the distance to either
         if l2 < EPSILON {
-            return (self.start-p).squared_length().sqrt().into();
+            return self.start.distance(&p).into();
         } else {
             let v1 = p - self.start;
             let v2 = self.end - self.start;
-            let mut dot: NativeFloat = 0.0;
-            for (i, _) in v1.into_iter().enumerate() {
-                dot = dot + v1.axis(i) * v2.axis(i);
-            }
-            let mut t = 0.0;
-            if dot / l2 < 1.0 {
-                t = dot/l2;
-            } 
-            if t < 0.0 {
-                t = 0.0;
-            }
+            let dot = v1.dot(&v2);
+            // clamp the projection onto the segment
+            let t = (dot / l2).clamp(0.0, 1.0);
             let projection = self.start + (self.end - self.start) * t;  // Projection falls on the segment
-            return (p-projection).squared_length().sqrt().into();
+            return p.distance(&projection).into();
         }
     }
 
+    /// Returns the distance_to_point() of the 2D point p with a sign, so it can be used for side tests:
+    /// positive if p lies left of the segment (looking from start to end), negative if it lies right of it.
+    /// Only the first two axes of the points are used for the sign.
+    pub fn signed_distance_to_point<F>(&self, p: P) -> F 
+    where 
+    F: Float + Add + Copy + Default + Into<NativeFloat>,
+    NativeFloat: Add + Into<F>,
+    {
+        debug_assert!(P::DIM == 2);
+        let distance: F = self.distance_to_point(p);
+        if (self.end - self.start).cross_2d(&(p - self.start)) < 0.0 {
+            return -distance;
+        }
+        return distance
+    }
+
     /// Sample the coordinate axis of the segment at t (expecting t between 0 and 1).
     pub fn axis<F>(&self, t: F, axis: usize) -> F 
     where
@@ -276,7 +322,7 @@ NativeFloat: Sub<NativeFloat, Output = NativeFloat>
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "f32")))]
 mod tests 
 {
     use super::*;
@@ -307,11 +353,55 @@ mod tests
         };
         // dist to start should be 4; dist to end should be 5
         let p1 = PointN::new([0f64, 5f64, 0f64]);
-        assert!( ((p1-line.start).squared_length().sqrt() - 4.0).abs() < EPSILON );
-        assert!( ((p1-line.end).squared_length().sqrt() - 5.0).abs() < EPSILON );
+        assert!( (p1.distance(&line.start) - 4.0).abs() < EPSILON );
+        assert!( (p1.distance(&line.end) - 5.0).abs() < EPSILON );
         // dist to midpoint (t=0.5) should be 1
         let p2 = PointN::new([1.5f64, 2f64, 0f64]);
-        assert!( ((p2-line.eval(0.5)).squared_length().sqrt() - 1.0).abs() < EPSILON );
+        assert!( (p2.distance(&line.eval(0.5)) - 1.0).abs() < EPSILON );
     }
    
-}
\ No newline at end of file
+
+
+    #[test]
+    fn line_segment_split_and_length() {
+        let line = LineSegment::new(PointN::new([1f64, 1f64]), PointN::new([4f64, 5f64]));
+        assert!(line.length::<f64>() == 5.0);
+        let (left, right) = line.split(0.2);
+        assert!(left.start == line.start && right.end == line.end);
+        assert!(left.end == right.start);
+        assert!(left.end.distance(&line.eval(0.2)) < EPSILON);
+        assert!((left.length::<f64>() - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn line_segment_signed_distance() {
+        let line = LineSegment::new(PointN::new([0f64, 0f64]), PointN::new([2f64, 0f64]));
+        assert!(line.signed_distance_to_point::<f64>(PointN::new([1f64, 3f64])) == 3.0);
+        assert!(line.signed_distance_to_point::<f64>(PointN::new([1f64, -3f64])) == -3.0);
+        // beyond the end points the distance is measured to the closest end point
+        assert!(line.signed_distance_to_point::<f64>(PointN::new([5f64, -4f64])) == -5.0);
+        assert!(line.signed_distance_to_point::<f64>(PointN::new([1f64, 0f64])) == 0.0);
+        // the unsigned distance
+        assert!(line.distance_to_point::<f64>(PointN::new([1f64, -3f64])) == 3.0);
+    }
+
+    #[test]
+    fn line_segment_intersection() {
+        let line = LineSegment::new(PointN::new([0f64, 0f64]), PointN::new([2f64, 2f64]));
+        let crossing = LineSegment::new(PointN::new([0f64, 2f64]), PointN::new([2f64, 0f64]));
+        let p = line.intersection(&crossing).unwrap();
+        assert!(p.distance(&PointN::new([1f64, 1f64])) < EPSILON);
+        assert!(crossing.intersection(&line).unwrap().distance(&p) < EPSILON);
+        // touching end points
+        let touching = LineSegment::new(PointN::new([2f64, 2f64]), PointN::new([3f64, 0f64]));
+        assert!(line.intersection(&touching) == Some(PointN::new([2f64, 2f64])));
+        // the lines intersect outside of the segments
+        let short = LineSegment::new(PointN::new([0f64, 2f64]), PointN::new([0.5f64, 1.5f64]));
+        assert!(line.intersection(&short).is_none());
+        // parallel and colinear segments
+        let parallel = LineSegment::new(PointN::new([0f64, 1f64]), PointN::new([2f64, 3f64]));
+        assert!(line.intersection(&parallel).is_none());
+        let colinear = LineSegment::new(PointN::new([1f64, 1f64]), PointN::new([3f64, 3f64]));
+        assert!(line.intersection(&colinear).is_none());
+    }
+}
@@ -0,0 +1,309 @@
+//! Conversion of a stroked path (a centerline with a width) into a closed outline which can be filled.
+//! Only 2D curves are supported, the first two axes of the points are used as x and y.
+//! The module requires the 'alloc' feature.
+
+use super::*;
+use super::point::Point;
+use super::cubic_bezier::CubicBezier;
+use super::arc::arc_to_beziers;
+
+use alloc::vec::Vec;
+
+/// Tolerance of the offset curves relative to the stroke width, see stroke_outline()
+const STROKE_OFFSET_TOLERANCE: NativeFloat = 1e-3;
+
+
+/// Shape of the open ends of a stroke
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CapStyle {
+    /// The stroke ends flat at the end points
+    Butt,
+    /// The stroke ends with a half circle around the end points
+    Round,
+    /// The stroke ends flat, extended by half the width beyond the end points
+    Square,
+}
+
+/// Shape of the outer corner where two segments of a stroke meet at an angle
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JoinStyle {
+    /// The offset segments are connected by a circular arc around the joint
+    Round,
+    /// The offset segments are extended until they meet. If the ratio of the miter length
+    /// to the stroke width exceeds the given limit (like the SVG stroke-miterlimit), a bevel is used instead.
+    Miter(NativeFloat),
+    /// The offset segments are connected by a straight line
+    Bevel,
+}
+
+
+/// Returns the outline of the path of connected segments stroked with the given width
+/// as a closed loop of cubic curves, which runs counter-clockwise.
+/// The loop consists of the offset of the right side of the path (see CubicBezier::offset()),
+/// the cap at the end, the offset of the left side in reverse and the cap at the start.
+/// Where the segments meet at an angle, the outer sides are connected according to join
+/// and the inner sides are connected through the joint with two straight lines.
+/// The inner sides overlap, so the outline has to be filled with the nonzero winding rule.
+/// Closed paths are treated like open ones, i.e. they get caps at the start and end.
+/// An empty path yields an empty outline.
+pub fn stroke_outline<P, F>(segments: &[CubicBezier<P>], width: F, cap: CapStyle, join: JoinStyle) -> Vec<CubicBezier<P>>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+F: Float
+    + Into<NativeFloat>,
+{
+    debug_assert!(P::DIM == 2);
+    let mut outline = Vec::new();
+    let (first, last) = match (segments.first(), segments.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return outline,
+    };
+    let half_width = width.into().abs() * 0.5;
+    let tolerance = half_width * 2.0 * STROKE_OFFSET_TOLERANCE;
+    let reversed: Vec<CubicBezier<P>> = segments.iter().rev().map(|s| s.reverse()).collect();
+
+    // the right side of a segment is the left side of the reversed segment
+    offset_side(&mut outline, segments, half_width, tolerance, join);
+    add_cap(&mut outline, last.end, last.end - last.normal(1.0) * half_width, last.tangent(1.0), half_width, cap);
+    offset_side(&mut outline, &reversed, half_width, tolerance, join);
+    let start_tangent = first.tangent(0.0) * -1.0;
+    add_cap(&mut outline, first.start, first.start + first.normal(0.0) * half_width, start_tangent, half_width, cap);
+    return outline
+}
+
+
+/// Appends the offsets of the segments to the right at half_width and the joins between them
+fn offset_side<P>(outline: &mut Vec<CubicBezier<P>>, segments: &[CubicBezier<P>], half_width: NativeFloat, tolerance: NativeFloat, join: JoinStyle)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            let previous = segments[i - 1];
+            let from = outline[outline.len() - 1].end;
+            let to = segment.start - segment.normal(0.0) * half_width;
+            let corner = Corner {
+                center: segment.start,
+                from,
+                to,
+                tangent_in: previous.tangent(1.0),
+                tangent_out: segment.tangent(0.0),
+            };
+            add_join(outline, corner, half_width, join);
+        }
+        outline.extend(segment.offset(-half_width, tolerance).iter());
+    }
+}
+
+
+/// Geometry of the joint where two segments of a stroke meet
+#[derive(Copy, Clone, Debug)]
+struct Corner<P> {
+    /// The common end point of the two segments
+    center: P,
+    /// The right offset of the end of the incoming segment
+    from: P,
+    /// The right offset of the start of the outgoing segment
+    to: P,
+    /// The tangent at the end of the incoming segment
+    tangent_in: P,
+    /// The tangent at the start of the outgoing segment
+    tangent_out: P,
+}
+
+
+/// Appends the connection between the right offsets of two segments meeting at the corner
+fn add_join<P>(outline: &mut Vec<CubicBezier<P>>, corner: Corner<P>, half_width: NativeFloat, join: JoinStyle)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let Corner { center, from, to, tangent_in, tangent_out } = corner;
+    if from.distance(&to) < EPSILON * half_width.max(1.0) {
+        return
+    }
+    // a right turn puts the right side on the inside of the corner
+    if tangent_in.cross_2d(&tangent_out) <= 0.0 {
+        outline.push(line(from, center));
+        outline.push(line(center, to));
+        return
+    }
+    match join {
+        JoinStyle::Round => add_arc(outline, center, from, to, half_width),
+        JoinStyle::Bevel => outline.push(line(from, to)),
+        JoinStyle::Miter(limit) => {
+            // the miter length relative to the width is 1/cos(φ/2) for the turning angle φ
+            let cos_half = ((1.0 + tangent_in.dot(&tangent_out)) * 0.5).max(0.0).sqrt();
+            if cos_half < EPSILON || 1.0 / cos_half > limit {
+                outline.push(line(from, to));
+                return
+            }
+            let bisector = (from - center + to - center).normalize();
+            let tip = center + bisector * (half_width / cos_half);
+            outline.push(line(from, tip));
+            outline.push(line(tip, to));
+        }
+    }
+}
+
+
+/// Appends the cap around center, starting at the right offset from and ending on the opposite side
+/// of center, for the stroke running in direction
+fn add_cap<P>(outline: &mut Vec<CubicBezier<P>>, center: P, from: P, direction: P, half_width: NativeFloat, cap: CapStyle)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let to = center * 2.0 - from;
+    match cap {
+        CapStyle::Butt => outline.push(line(from, to)),
+        CapStyle::Round => add_arc(outline, center, from, to, half_width),
+        CapStyle::Square => {
+            let extension = direction * half_width;
+            outline.push(line(from, from + extension));
+            outline.push(line(from + extension, to + extension));
+            outline.push(line(to + extension, to));
+        }
+    }
+}
+
+
+/// Appends the shorter circular arc around center from from to to, which is counter-clockwise for half circles
+fn add_arc<P>(outline: &mut Vec<CubicBezier<P>>, center: P, from: P, to: P, radius: NativeFloat)
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let pi = core::f64::consts::PI as NativeFloat;
+    let angle = |p: P| (p.axis(1) - center.axis(1)).atan2(p.axis(0) - center.axis(0));
+    let start_angle = angle(from);
+    let mut sweep = angle(to) - start_angle;
+    if sweep <= -pi {
+        sweep += 2.0 * pi;
+    } else if sweep > pi {
+        sweep -= 2.0 * pi;
+    }
+    let mut arc = arc_to_beziers(center, radius, start_angle, start_angle + sweep);
+    // connect to the adjacent curves exactly
+    arc[0].start = from;
+    let last = arc.len() - 1;
+    arc[last].end = to;
+    outline.extend(arc);
+}
+
+
+/// Returns the straight line from start to end as a cubic curve
+fn line<P>(start: P, end: P) -> CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    let third = (end - start) * (1.0 / 3.0);
+    CubicBezier::new(start, start + third, end - third, end)
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+    use super::point_generic::PointN;
+    use super::cubic_bezier::signed_area;
+
+    fn assert_closed(outline: &[CubicBezier<PointN<f64, 2>>]) {
+        for pair in outline.windows(2) {
+            assert!(pair[0].end.distance(&pair[1].start) < 1e-12);
+        }
+        assert!(outline[outline.len() - 1].end.distance(&outline[0].start) < 1e-12);
+    }
+
+    #[test]
+    fn straight_segment() {
+        let segment = line(PointN::new([0f64, 0f64]), PointN::new([3f64, 0f64]));
+
+        // a rectangle of length 3 and width 2
+        let outline = stroke_outline(&[segment], 2.0, CapStyle::Butt, JoinStyle::Round);
+        assert_closed(&outline);
+        for curve in outline.iter() {
+            for p in [curve.start, curve.ctrl1, curve.ctrl2, curve.end].iter() {
+                let on_side = (p.axis(1).abs() - 1.0).abs() < 1e-12 && p.axis(0) >= -1e-12 && p.axis(0) <= 3.0 + 1e-12;
+                let on_cap = (p.axis(0).abs() < 1e-12 || (p.axis(0) - 3.0).abs() < 1e-12) && p.axis(1).abs() <= 1.0 + 1e-12;
+                assert!(on_side || on_cap);
+            }
+        }
+        assert!((signed_area::<_, f64>(&outline) - 6.0).abs() < 1e-12);
+
+        let outline = stroke_outline(&[segment], 2.0, CapStyle::Square, JoinStyle::Round);
+        assert_closed(&outline);
+        assert!((signed_area::<_, f64>(&outline) - 10.0).abs() < 1e-12);
+
+        // all points of the outline are at half the width from the segment
+        let outline = stroke_outline(&[segment], 2.0, CapStyle::Round, JoinStyle::Round);
+        assert_closed(&outline);
+        assert!((signed_area::<_, f64>(&outline) - (6.0 + core::f64::consts::PI)).abs() < 1e-3);
+        let nsteps: usize = 20;
+        for curve in outline.iter() {
+            for t in 0..=nsteps {
+                let t = t as f64 * 1f64/(nsteps as f64);
+                let p = curve.eval(t);
+                let distance = segment.project::<f64>(p).1;
+                assert!((distance - 1.0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn joins() {
+        // a left turn by 90°, so the right side is on the outside
+        let path = [
+            line(PointN::new([0f64, 0f64]), PointN::new([2f64, 0f64])),
+            line(PointN::new([2f64, 0f64]), PointN::new([2f64, 2f64])),
+        ];
+        // the inner corner [1,2]x[0,1] is covered twice
+        let expected_area = |corner: f64| 4.0 + 4.0 + corner;
+        let miter = stroke_outline(&path, 2.0, CapStyle::Butt, JoinStyle::Miter(4.0));
+        assert_closed(&miter);
+        assert!((signed_area::<_, f64>(&miter) - expected_area(1.0)).abs() < 1e-12);
+        assert!(miter.iter().any(|c| c.end.distance(&PointN::new([3f64, -1f64])) < 1e-12));
+
+        // the miter length of a right angle is √2 times the width
+        let limited = stroke_outline(&path, 2.0, CapStyle::Butt, JoinStyle::Miter(1.4));
+        assert!((signed_area::<_, f64>(&limited) - expected_area(0.5)).abs() < 1e-12);
+        let bevel = stroke_outline(&path, 2.0, CapStyle::Butt, JoinStyle::Bevel);
+        assert_closed(&bevel);
+        assert!((signed_area::<_, f64>(&bevel) - expected_area(0.5)).abs() < 1e-12);
+        let round = stroke_outline(&path, 2.0, CapStyle::Butt, JoinStyle::Round);
+        assert_closed(&round);
+        assert!((signed_area::<_, f64>(&round) - expected_area(0.25 * core::f64::consts::PI)).abs() < 1e-3);
+
+        // smooth joints don't need a join
+        let straight = [
+            line(PointN::new([0f64, 0f64]), PointN::new([1f64, 0f64])),
+            line(PointN::new([1f64, 0f64]), PointN::new([3f64, 0f64])),
+        ];
+        let outline = stroke_outline(&straight, 2.0, CapStyle::Butt, JoinStyle::Round);
+        assert_closed(&outline);
+        assert!(outline.len() == 6);
+        assert!(stroke_outline::<PointN<f64, 2>, f64>(&[], 2.0, CapStyle::Round, JoinStyle::Round).is_empty());
+    }
+}
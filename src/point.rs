@@ -1,4 +1,5 @@
-use core::ops::{Add, Sub};
+use core::ops::{Add, Sub, Mul};
+use num_traits::{Float, One, Zero};
 /// The Point trait is the only interface on which the library relies.
 /// The associated constant DIM is necessary so that the memory layout of
 /// its implementing type can be made known to the library, whenever new instances are returned.
@@ -11,7 +12,135 @@ pub trait Point: Add + Sub + Copy + PartialEq + Default + IntoIterator
     // TODO remove, use mutable iterator instead (?)
     fn axis(&self, index: usize) -> Self::Scalar;
 
+    // Sets the component of the Point on its axis corresponding to index e.g. [0, 1, 2] -> [x, y, z]
+    // This is the only way for the library to construct new points component-wise (e.g. normals)
+    // Required since 0.2.0 (a breaking change for implementors, see CHANGELOG.md): the other methods
+    // only read points, so there is no way to provide a default implementation
+    fn set_axis(&mut self, index: usize, value: Self::Scalar);
+
+    // Returns the number of components of the Point, which is always Self::DIM.
+    // Useful where only a value is at hand, e.g. behind a generic or trait object abstraction
+    fn dim(&self) -> usize {
+        Self::DIM
+    }
+
     // Returns the squared L2-Norm of the Point interpreted as a Vector
     // TODO this could be moved into the library because computability is ensured by its existing trait bounds
     fn squared_length(&self) -> Self::Scalar;
+
+    // Returns the L2-Norm of the Point interpreted as a Vector
+    fn length(&self) -> Self::Scalar
+    where
+    Self::Scalar: Float,
+    {
+        self.squared_length().sqrt()
+    }
+
+    // Returns the euclidean distance between self and other
+    fn distance(&self, other: &Self) -> Self::Scalar
+    where
+    Self: Sub<Self, Output = Self>,
+    Self::Scalar: Float,
+    {
+        (*self - *other).length()
+    }
+
+    // Returns the Point scaled to unit length when interpreted as a Vector
+    // If the length is below the machine epsilon, a zero vector (the default) is returned instead of NaN
+    fn normalize(&self) -> Self
+    where
+    Self: Mul<Self::Scalar, Output = Self>,
+    Self::Scalar: Float,
+    {
+        let length = self.length();
+        if length < Self::Scalar::epsilon() {
+            return Self::default()
+        }
+        *self * (Self::Scalar::one() / length)
+    }
+
+    // Returns the dot product (sum of component-wise products) of self and other
+    fn dot(&self, other: &Self) -> Self::Scalar
+    where
+    Self::Scalar: Float,
+    {
+        let mut dot = Self::Scalar::zero();
+        for i in 0..Self::DIM {
+            dot = dot + self.axis(i) * other.axis(i);
+        }
+        dot
+    }
+
+    // Returns the 2D cross product (the z component of the 3D cross product) x0*y1 - y0*x1 of self and other
+    // Only the first two axes are used, so it requires DIM >= 2
+    fn cross_2d(&self, other: &Self) -> Self::Scalar
+    where
+    Self::Scalar: Float,
+    {
+        debug_assert!(Self::DIM >= 2);
+        self.axis(0) * other.axis(1) - self.axis(1) * other.axis(0)
+    }
+
+    // Returns the linear interpolation between self (t=0) and other (t=1)
+    // Written as self * (1-t) + other * t so that the endpoints are reproduced exactly
+    fn interpolate(&self, other: &Self, t: Self::Scalar) -> Self
+    where
+    Self: Add<Self, Output = Self>
+        + Mul<Self::Scalar, Output = Self>,
+    Self::Scalar: Float,
+    {
+        *self * (Self::Scalar::one() - t) + *other * t
+    }
+
+    // Returns the point halfway between self and other, without subtracting the points
+    fn midpoint(&self, other: &Self) -> Self
+    where
+    Self: Add<Self, Output = Self>
+        + Mul<Self::Scalar, Output = Self>,
+    Self::Scalar: Float,
+    {
+        let half = Self::Scalar::one() / (Self::Scalar::one() + Self::Scalar::one());
+        (*self + *other) * half
+    }
+}
+
+/// Returns the centroid (the average) of the points, or the origin (P::default()) for an empty slice
+pub fn centroid<P>(points: &[P]) -> P
+where
+P: Point
+    + Add<P, Output = P>
+    + Mul<P::Scalar, Output = P>,
+P::Scalar: Float,
+{
+    let mut sum = P::default();
+    let mut count = P::Scalar::zero();
+    for p in points {
+        sum = sum + *p;
+        count = count + P::Scalar::one();
+    }
+    if points.is_empty() {
+        return sum
+    }
+    sum * (P::Scalar::one() / count)
+}
+
+/// Returns the bounding box of the points as an array of (min, max) tuples for each dimension.
+/// The slice must not be empty.
+pub fn bounding_box<P, F>(points: &[P]) -> [(F, F); P::DIM]
+where
+P: Point,
+P::Scalar: Float + Into<F>,
+F: Float,
+{
+    let mut bounds = [(F::zero(), F::zero()); P::DIM];
+    for (dim, bound) in bounds.iter_mut().enumerate() {
+        let mut min = points[0].axis(dim);
+        let mut max = min;
+        for p in points.iter() {
+            min = min.min(p.axis(dim));
+            max = max.max(p.axis(dim));
+        }
+        *bound = (min.into(), max.into());
+    }
+    return bounds
 }
@@ -1,3 +1,7 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Div, Index, IndexMut, Neg};
+
 use super::*;
 use num_traits::Float;
 use super::point::Point;
@@ -10,12 +14,73 @@ use super::point::Point;
 /// the point trait, so you are free to use your own 
 /// Point/Coord/Vec structures instead by implementing the (small) trait
 #[derive(Debug, Copy, Clone)]
-pub struct PointN<T, const N: usize>([T; N]);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "T: Serialize",
+                                          deserialize = "T: Deserialize<'de> + Default + Copy")))]
+pub struct PointN<T, const N: usize>(
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_array"))]
+    [T; N]
+);
 
 impl<T, const N: usize> PointN<T, N> {
     pub fn new(array: [T;N]) -> Self {
         return PointN(array)
     }
+
+    /// Returns the point with f applied to each component
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> PointN<U, N>
+    where
+    T: Copy,
+    U: Default + Copy,
+    {
+        let mut res = [U::default(); N];
+        for i in 0..N {
+            res[i] = f(self.0[i]);
+        }
+        return PointN(res)
+    }
+
+    /// Returns the point combining the components of self and other with f
+    pub fn zip_with<U, V>(self, other: PointN<U, N>, f: impl Fn(T, U) -> V) -> PointN<V, N>
+    where
+    T: Copy,
+    U: Copy,
+    V: Default + Copy,
+    {
+        let mut res = [V::default(); N];
+        for i in 0..N {
+            res[i] = f(self.0[i], other.0[i]);
+        }
+        return PointN(res)
+    }
+
+    /// Returns the point with the smaller of the components of self and other on each axis
+    /// (a component which is NaN is replaced by the other one if that isn't NaN)
+    pub fn component_min(self, other: Self) -> Self
+    where
+    T: PartialOrd + Default + Copy,
+    {
+        // a is NaN if it isn't comparable to itself
+        self.zip_with(other, |a, b| if b < a || a.partial_cmp(&a).is_none() { b } else { a })
+    }
+
+    /// Returns the point with the larger of the components of self and other on each axis
+    /// (a component which is NaN is replaced by the other one if that isn't NaN)
+    pub fn component_max(self, other: Self) -> Self
+    where
+    T: PartialOrd + Default + Copy,
+    {
+        self.zip_with(other, |a, b| if b > a || a.partial_cmp(&a).is_none() { b } else { a })
+    }
+
+    /// Returns the point with each component clamped to the interval between the components of lo and hi,
+    /// i.e. the closest point inside of the box spanned by lo and hi (if lo <= hi on each axis)
+    pub fn clamp(self, lo: Self, hi: Self) -> Self
+    where
+    T: PartialOrd + Default + Copy,
+    {
+        self.component_max(lo).component_min(hi)
+    }
 }
 
 /// Initialize with the Default value for the underlying type
@@ -25,15 +90,22 @@ impl<T: Default + Copy, const N: usize> Default for PointN<T, N> {
     }
 }
 
-impl<T, const N: usize> PartialEq for PointN<T, N> 
-where T: PartialOrd {
+impl<T, const N: usize> PartialEq for PointN<T, N>
+where T: PartialEq {
     fn eq(&self, other: &Self) -> bool {
-        for i in 0..N {
-            if self.0[i] != other.0[i] {
-                return false
-            }
-        }
-        return true
+        self.0 == other.0
+    }
+}
+
+/// Points with integer coordinates can be compared for equality and hashed, e.g. as keys of a grid.
+/// Float coordinates only implement PartialEq (as NaN is not equal to itself).
+impl<T, const N: usize> Eq for PointN<T, N>
+where T: Eq {}
+
+impl<T, const N: usize> Hash for PointN<T, N>
+where T: Hash {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
     }
 }
 
@@ -89,6 +161,39 @@ where
 }
 
 
+impl<T, const N:usize, U> Div<U> for PointN<T, N>
+where
+    T: Div<U,Output=T> + Clone + Copy,
+    U: Clone + Copy,
+{
+    type Output = PointN<T, N>;
+
+    fn div(self, _rhs: U) -> PointN<T, N> {
+        let mut res = self.clone();
+        for i in 0..res.0.len() {
+            res.0[i] = res.0[i] / _rhs;
+        }
+        res
+    }
+}
+
+
+impl<T, const N: usize> Neg for PointN<T, N>
+where
+    T: Neg<Output=T> + Clone + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> PointN<T, N> {
+        let mut res = self.clone();
+        for i in 0..N {
+            res.0[i] = -self.0[i];
+        }
+        res
+    }
+}
+
+
 impl<T, const N: usize> IntoIterator for PointN<T, N> {
     type Item = T;
     type IntoIter = core::array::IntoIter<Self::Item, N>;
@@ -98,6 +203,63 @@ impl<T, const N: usize> IntoIterator for PointN<T, N> {
     }
 }
 
+impl<T, const N: usize> Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+/// Formats the point as a parenthesized, comma separated list of its components, e.g. "(1, 2.5)".
+/// Format options like the precision ("{:.2}") are applied to each component.
+impl<T, const N: usize> fmt::Display for PointN<T, N>
+where
+T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            c.fmt(f)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for PointN<T, N> {
+    fn from(array: [T; N]) -> Self {
+        PointN(array)
+    }
+}
+
+impl<T, const N: usize> From<PointN<T, N>> for [T; N] {
+    fn from(p: PointN<T, N>) -> Self {
+        p.0
+    }
+}
+
+impl<T> From<(T, T)> for PointN<T, 2> {
+    fn from(tuple: (T, T)) -> Self {
+        PointN([tuple.0, tuple.1])
+    }
+}
+
+impl<T> From<PointN<T, 2>> for (T, T) {
+    fn from(p: PointN<T, 2>) -> Self {
+        let [x, y] = p.0;
+        (x, y)
+    }
+}
+
 // impl<'a, T, const N: usize> IntoIterator for &'a mut PointN<T, N> {
 //     type Item = &'a mut T;
 //     type IntoIter = slice::IterMut<'a, T>;
@@ -123,6 +285,10 @@ NativeFloat: Add + Into<T>,
         return self.0[index].into()
     }
 
+    fn set_axis(&mut self, index: usize, value: Self::Scalar) {
+        self.0[index] = value.into();
+    }
+
     fn squared_length(&self) -> Self::Scalar {
         let mut sqr_dist: Self::Scalar = 0.0;
         for i in 0..N {
@@ -131,4 +297,248 @@ NativeFloat: Add + Into<T>,
         return sqr_dist
     }
 
-}
\ No newline at end of file
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn interpolate_endpoints() {
+        let p = PointN::new([0.1f64, -3.7f64, 1e-3f64]);
+        let q = PointN::new([0.3f64, 2.9f64, 1e5f64]);
+        // the endpoints must be reproduced exactly
+        assert!(p.interpolate(&q, 0.0) == p);
+        assert!(p.interpolate(&q, 1.0) == q);
+        // the midpoint has equal distance to both endpoints
+        let mid = p.interpolate(&q, 0.5);
+        assert!(((mid - p).squared_length() - (mid - q).squared_length()).abs() < 1e-6);
+    }
+
+
+    #[test]
+    fn dim() {
+        let p = PointN::new([0.1f64, -3.7f64, 1e-3f64]);
+        assert!(p.dim() == <PointN<f64, 3> as Point>::DIM);
+        assert!(p.dim() == 3);
+        assert!(PointN::new([1f64, 2f64]).dim() == 2);
+    }
+
+    #[test]
+    fn midpoint_and_centroid() {
+        use super::point::centroid;
+        let p = PointN::new([0.1f64, -3.7f64, 1e-3f64]);
+        let q = PointN::new([0.3f64, 2.9f64, 1e5f64]);
+        let mid = p.midpoint(&q);
+        assert!(mid.distance(&PointN::new([0.2f64, -0.4f64, 50000.0005f64])) < 1e-9);
+        assert!(mid.distance(&p.interpolate(&q, 0.5)) < 1e-9);
+        assert!(p.midpoint(&p) == p);
+
+        // the corners of a square average to its center
+        let square = [
+            PointN::new([1f64, 1f64]),
+            PointN::new([3f64, 1f64]),
+            PointN::new([3f64, 3f64]),
+            PointN::new([1f64, 3f64]),
+        ];
+        assert!(centroid(&square) == PointN::new([2f64, 2f64]));
+        assert!(centroid(&square[..1]) == square[0]);
+        assert!(centroid::<PointN<f64, 2>>(&[]) == PointN::default());
+    }
+
+    #[test]
+    fn component_min_max_clamp() {
+        let p = PointN::new([0.1f64, -3.7f64, 1e-3f64]);
+        let q = PointN::new([0.3f64, 2.9f64, -1e5f64]);
+        assert!(p.component_min(q) == PointN::new([0.1f64, -3.7f64, -1e5f64]));
+        assert!(p.component_max(q) == PointN::new([0.3f64, 2.9f64, 1e-3f64]));
+        assert!(q.component_min(p) == p.component_min(q));
+        assert!(p.component_min(p) == p);
+
+        let lo = PointN::new([0f64, -1f64, -1f64]);
+        let hi = PointN::new([1f64, 1f64, 1f64]);
+        assert!(p.clamp(lo, hi) == PointN::new([0.1f64, -1f64, 1e-3f64]));
+        assert!(q.clamp(lo, hi) == PointN::new([0.3f64, 1f64, -1f64]));
+
+        // NaN components are replaced
+        let nan = PointN::new([f64::NAN, 1f64, 2f64]);
+        assert!(nan.component_min(p) == PointN::new([0.1f64, -3.7f64, 1e-3f64]));
+        assert!(p.component_max(nan) == PointN::new([0.1f64, 1f64, 2f64]));
+
+        // also for integer components, e.g. to merge bounding boxes of grid cells
+        let a = PointN::new([1i32, 5i32, -2i32]);
+        let b = PointN::new([3i32, -5i32, 0i32]);
+        assert!(a.component_min(b) == PointN::new([1i32, -5i32, -2i32]));
+        assert!(a.component_max(b) == PointN::new([3i32, 5i32, 0i32]));
+        let clamped = PointN::new([7i32, -9i32, 0i32]).clamp(a.component_min(b), a.component_max(b));
+        assert!(clamped == PointN::new([3i32, -5i32, 0i32]));
+    }
+
+    #[test]
+    fn dot_and_cross() {
+        let x = PointN::new([2f64, 0f64]);
+        let y = PointN::new([0f64, 3f64]);
+        // orthogonal vectors
+        assert!(x.dot(&y) == 0.0);
+        assert!(x.cross_2d(&y) == 6.0);
+        assert!(y.cross_2d(&x) == -6.0);
+        // parallel vectors
+        let p = PointN::new([1.5f64, -2f64]);
+        let q = p * -3.0;
+        assert!(p.cross_2d(&q) == 0.0);
+        assert!(p.dot(&q) == -3.0 * p.squared_length());
+        assert!(p.dot(&p) == p.squared_length());
+        // dot product in 3D
+        let u = PointN::new([1f64, 2f64, 3f64]);
+        let v = PointN::new([-4f64, 5f64, 0.5f64]);
+        assert!(u.dot(&v) == 7.5);
+    }
+
+
+    #[test]
+    fn length_and_distance() {
+        let p = PointN::new([3f64, 4f64]);
+        assert!(p.length() == 5.0);
+        assert!(p.distance(&p) == 0.0);
+        let q = PointN::new([0f64, 1f64, 0f64]);
+        let r = PointN::new([3f64, 1f64, 4f64]);
+        assert!(q.distance(&r) == 5.0);
+        assert!(q.distance(&r) == r.distance(&q));
+    }
+
+
+    #[test]
+    fn normalize() {
+        let max_err = 1e-15;
+        for p in [PointN::new([3f64, 4f64, 0f64]),
+                  PointN::new([-1e-3f64, 2e-4f64, 7e-5f64]),
+                  PointN::new([1e5f64, -3.3e4f64, 42f64])].iter() {
+            let n = p.normalize();
+            assert!((n.length() - 1.0).abs() < max_err);
+            // same direction as the original
+            assert!((n.dot(p) - p.length()).abs() < max_err * p.length());
+        }
+        // the zero vector stays zero
+        let zero = PointN::new([0f64, 0f64, 0f64]);
+        assert!(zero.normalize() == zero);
+    }
+
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let p = PointN::new([0.1f64, -3.7f64, 1e-3f64]);
+        let json = serde_json::to_string(&p).unwrap();
+        assert!(json == "[0.1,-3.7,0.001]");
+        let q: PointN<f64, 3> = serde_json::from_str(&json).unwrap();
+        assert!(p == q);
+        // wrong number of components must be rejected
+        assert!(serde_json::from_str::<PointN<f64, 3>>("[0.1,-3.7]").is_err());
+        assert!(serde_json::from_str::<PointN<f64, 3>>("[0.1,-3.7,1.0,2.0]").is_err());
+    }
+
+
+    #[test]
+    fn index() {
+        let mut p = PointN::new([1f64, 2f64, 3f64]);
+        assert!(p[0] == 1.0 && p[1] == 2.0 && p[2] == 3.0);
+        p[1] = -4.5;
+        p[2] += 1.0;
+        assert!(p[1] == -4.5 && p[2] == 4.0);
+        assert!(p.axis(1) == -4.5);
+        assert!(p == PointN::new([1f64, -4.5f64, 4f64]));
+    }
+
+
+    #[test]
+    fn map_and_zip_with() {
+        let p = PointN::new([1f64, -2f64, 3.5f64]);
+        assert!(p.map(|x| x * 2.0) == PointN::new([2f64, -4f64, 7f64]));
+        assert!(p.map(|x| x * 2.0) == p * 2.0);
+        // the component type may change
+        let rounded: PointN<i32, 3> = p.map(|x| x.round() as i32);
+        assert!(rounded[0] == 1 && rounded[1] == -2 && rounded[2] == 4);
+
+        let q = PointN::new([0f64, 5f64, -1f64]);
+        assert!(p.zip_with(q, f64::max) == PointN::new([1f64, 5f64, 3.5f64]));
+        assert!(p.zip_with(q, f64::min) == PointN::new([0f64, -2f64, -1f64]));
+    }
+
+
+    #[test]
+    fn div() {
+        let p = PointN::new([3f64, -1f64, 0f64]);
+        assert!(p / 2.0 == PointN::new([1.5f64, -0.5f64, 0f64]));
+        assert!(p / 2.0 == p * 0.5);
+        // integer division truncates each component
+        assert!(PointN::new([7i32, -4i32]) / 2 == PointN::new([3i32, -2i32]));
+    }
+
+
+    #[test]
+    fn neg() {
+        let p = PointN::new([3f64, -1f64, 0.25f64]);
+        assert!(-p == PointN::new([-3f64, 1f64, -0.25f64]));
+        assert!(-(-p) == p);
+        assert!(-p == p * -1.0);
+    }
+
+
+    #[test]
+    fn array_and_tuple_conversions() {
+        let array = [1f64, -2f64, 3.5f64];
+        let p: PointN<f64, 3> = array.into();
+        assert!(p == PointN::new(array));
+        let back: [f64; 3] = p.into();
+        assert!(back == array);
+
+        let tuple = (0.5f64, -7f64);
+        let p = PointN::from(tuple);
+        assert!(p == PointN::new([0.5f64, -7f64]));
+        let (x, y) = p.into();
+        assert!((x, y) == tuple);
+    }
+
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display() {
+        use alloc::format;
+        let p = PointN::new([1f64, 2.5f64]);
+        assert!(format!("{}", p) == "(1, 2.5)");
+        assert!(format!("{:.2}", p) == "(1.00, 2.50)");
+        assert!(format!("{:.1}", PointN::new([-0.25f64, 3f64, 1e3f64])) == "(-0.2, 3.0, 1000.0)");
+    }
+
+
+    #[test]
+    fn hash_and_eq() {
+        extern crate std;
+        use std::collections::HashSet;
+        let mut grid = HashSet::new();
+        assert!(grid.insert(PointN::new([1i32, 2i32])));
+        assert!(grid.insert(PointN::new([2i32, 1i32])));
+        assert!(grid.insert(PointN::new([-1i32, 2i32])));
+        // already contained
+        assert!(!grid.insert(PointN::new([1i32, 2i32])));
+        assert!(grid.len() == 3);
+        assert!(grid.contains(&PointN::new([2i32, 1i32])));
+        assert!(!grid.contains(&PointN::new([0i32, 0i32])));
+    }
+
+
+    #[test]
+    fn partial_eq_without_order() {
+        // a coordinate type which can only be compared for equality
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        enum Cell { Empty, Wall }
+        let p = PointN::new([Cell::Empty, Cell::Wall]);
+        assert!(p == PointN::new([Cell::Empty, Cell::Wall]));
+        assert!(p != PointN::new([Cell::Wall, Cell::Wall]));
+        // float points still compare per component
+        assert!(PointN::new([0f64, 1f64]) == PointN::new([-0f64, 1f64]));
+        assert!(PointN::new([f64::NAN, 1f64]) != PointN::new([f64::NAN, 1f64]));
+    }
+}
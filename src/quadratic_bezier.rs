@@ -2,10 +2,13 @@ use core::default::Default;
 
 use super::*;
 use super::point::Point;
+use super::affine::Affine2;
 use super::line::LineSegment; 
+use super::roots::quadratic_roots;
 //use super::cubic_bezier::CubicBezier;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuadraticBezier<P>
 {
     pub(crate) start:  P,
@@ -31,6 +34,11 @@ P: Add + Sub + Copy
         }
     }
 
+    /// Returns the curve transformed by m by applying it to each control point
+    pub fn transform(&self, m: &Affine2) -> Self {
+        QuadraticBezier::new(m.apply(self.start), m.apply(self.ctrl), m.apply(self.end))
+    }
+
     /// Evaluates the quadratic bezier curve at t using direct evaluation, which may not be numerically stable
     pub fn eval<F>(&self, t: F) -> P 
     where 
@@ -52,7 +60,7 @@ P: Add + Sub + Copy
             + self.end * t2
     }
 
-    /// Evaluates the cubic bezier curve at t using the numerically stable De Casteljau algorithm
+    /// Evaluates the quadratic bezier curve at t using the numerically stable De Casteljau algorithm
     pub fn eval_casteljau<F>(&self, t: F) -> P
     where 
     F: Float,
@@ -72,6 +80,7 @@ P: Add + Sub + Copy
         return ctrl_2ab
     }
 
+    /// Splits the curve at t into two sub-curves covering [0, t] and [t, 1] of the original curve
     pub fn split<F>(&self, t: F) -> (Self, Self)
     where
     F: Float,
@@ -240,59 +249,19 @@ P: Add + Sub + Copy
     {
         let stepsize: NativeFloat = 1.0/(nsteps as NativeFloat);
         let mut arclen: NativeFloat = 0.0;
-        for t in 1..nsteps {
+        for t in 0..nsteps {
             let t = t as NativeFloat * 1.0.into()/(nsteps as NativeFloat).into();
             let p1 = self.eval_casteljau(t);
             let p2 = self.eval_casteljau(t+stepsize.into());
 
-            arclen = arclen + (p1-p2).squared_length().sqrt();
+            arclen = arclen + p1.distance(&p2);
         
         }
         return arclen.into()
     }
 
 
-    /// Solve for the roots of the polynomial at^2 + bt + c
-    /// Returns an ArrayVec of roots in the order
-    /// needs to be called for x and y components separately
-    pub(crate) fn real_roots<F>(&self, a: F, b: F, c: F) -> ArrayVec<[F; 2]>
-    where
-    F:  Float
-        + Default,
-    P:  Sub<P, Output = P>
-        + Add<P, Output = P>
-        + Mul<NativeFloat, Output = P>,
-        NativeFloat: Sub<F, Output = F> 
-        + Add<F, Output = F>
-        + Mul<F, Output = F>
-        + Float
-        + Into<F>
-    {
-
-        let mut result = ArrayVec::new();
-
-        // check if can be handled below quadratic order
-        if a.abs() < EPSILON.into() {
-            if b.abs() < EPSILON.into() {
-                // no solutions
-                return result;
-            }
-            // is linear equation
-            result.push(-c / b);
-            return result;
-        }
-        // is quadratic equation
-        let delta = b * b - 4.0.into() * a * c;
-        if delta > 0.0.into() {
-            let sqrt_delta = delta.sqrt();
-            result.push((-b - sqrt_delta) / (2.0.into() * a));
-            result.push((-b + sqrt_delta) / (2.0.into() * a));
-        } else if delta.abs() < EPSILON.into() {
-            result.push(-b / (2.0.into() * a));
-        }
-        return result;
-    }
-
+    /// Returns the line segment from start to end of the curve
     pub fn baseline(&self) -> LineSegment<P> {
         LineSegment {
             start: self.start,
@@ -301,7 +270,8 @@ P: Add + Sub + Copy
     }
 
 
-
+    /// Returns true if the control point lies within 'tolerance' of the baseline, i.e. the curve is a straight line.
+    /// A curve whose start and end coincide is never considered linear, as its baseline has no direction.
     pub fn is_linear<F>(&self, tolerance: F) -> bool 
     where
     F: Float
@@ -385,17 +355,16 @@ P: Add + Sub + Copy
             return result
         }
         // these are just the x or y components of the points
-        let a = self.start.axis(axis) 
-                    + self.ctrl.axis(axis) * -2.0.into() 
-                    + self.end.axis(axis).into();
-        let b = self.start.axis(axis) * -2.0.into() 
-                    + self.ctrl.axis(axis) * 2.0.into();
-        let c = self.start.axis(axis) - value;
-
-        let roots = self.real_roots(a, b, c);
-        for root in roots {
-            if root > 0.0.into() && root < 1.0.into() {
-                result.push(root);
+        let a = self.start.axis(axis)
+                    - 2.0 * self.ctrl.axis(axis)
+                    + self.end.axis(axis);
+        let b = -2.0 * self.start.axis(axis)
+                    + 2.0 * self.ctrl.axis(axis);
+        let c = self.start.axis(axis) - value.into();
+
+        for root in quadratic_roots(a, b, c) {
+            if root > 0.0 && root < 1.0 {
+                result.push(root.into());
             }
         }
 
@@ -454,7 +423,7 @@ P: Add + Sub + Copy
 
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "f32")))]
 mod tests 
 {
     use super::*;
@@ -575,6 +544,16 @@ mod tests
         }
     }
 
+    #[test]
+    fn default_is_origin() {
+        let bezier: QuadraticBezier<PointN<f64, 2>> = QuadraticBezier::default();
+        let nsteps: usize = 10;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            assert!(bezier.eval(t) == PointN::new([0f64, 0f64]));
+        }
+    }
+
     #[test]
     fn split_equivalence() {
         // chose some arbitrary control points and construct a cubic bezier
@@ -589,8 +568,7 @@ mod tests
         // compare left and right subcurves with parent curve
         // this is tricky as we have to map t->t/2 (for left) which will 
         // inevitably contain rounding errors from floating point ops.
-        // instead, take the difference of the two points which must not exceed the absolute error
-        // TODO update test to use norm() instead, once implemented for Point (maybe as trait?)
+        // instead, take the distance of the two points which must not exceed the absolute error
         let max_err = 1e-14;
         let nsteps: usize = 1000;                                      
         for t in 0..=nsteps {
@@ -598,17 +576,30 @@ mod tests
             //dbg!(bezier.eval(t/2.0));
             //dbg!(left.eval(t));
             // left
-            let mut err = bezier.eval(t/2.0) - left.eval(t);
-            for axis in err {
-                assert!(axis.abs() < max_err);
-            }
+            assert!(bezier.eval(t/2.0).distance(&left.eval(t)) < max_err);
             // right
-            err = bezier.eval((t*0.5)+0.5) - right.eval(t);
-            //dbg!(err);
-            for axis in err {
-                assert!(axis.abs() < max_err);
-            }
+            assert!(bezier.eval((t*0.5)+0.5).distance(&right.eval(t)) < max_err);
+        }
+    }
+
+
+    #[test]
+    fn is_linear() {
+        let start = PointN::new([0f64, 0f64]);
+        let end = PointN::new([4f64, 2f64]);
+        for ctrl in [[2f64, 1f64], [1f64, 0.5f64], [3.9f64, 1.95f64]].iter() {
+            let straight = QuadraticBezier::new(start, PointN::new(*ctrl), end);
+            assert!(straight.is_linear(1e-12));
+            assert!(straight.baseline() == LineSegment::new(start, end));
         }
+        // the curve overshoots the end point if the control point is on the line beyond the segment
+        assert!(!QuadraticBezier::new(start, PointN::new([6f64, 3f64]), end).is_linear(1e-12));
+        let curved = QuadraticBezier::new(start, PointN::new([2f64, 1.1f64]), end);
+        assert!(!curved.is_linear(1e-3));
+        // the deviation of the control point is 0.1 * cos(atan(0.5))
+        assert!(curved.is_linear(0.09));
+        // a closed curve has no baseline direction
+        assert!(!QuadraticBezier::new(start, PointN::new([2f64, 1f64]), start).is_linear(1e-12));
     }
 
 
@@ -639,4 +630,47 @@ mod tests
 
         }
     }
+
+
+    #[test]
+    fn eval_endpoints() {
+        let bezier = QuadraticBezier::new(
+            PointN::new([0f64,  1.77f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let max_err = 1e-14;
+        for err in [bezier.eval(0.0) - bezier.start,
+                    bezier.eval(1.0) - bezier.end,
+                    bezier.eval_casteljau(0.0) - bezier.start,
+                    bezier.eval_casteljau(1.0) - bezier.end].iter() {
+            for axis in *err {
+                assert!(axis.abs() < max_err);
+            }
+        }
+    }
+
+    #[test]
+    fn derivative_analytic() {
+        let bezier = QuadraticBezier::new(
+            PointN::new([0f64,  1.77f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        let derivative = bezier.derivative::<f64>();
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            // B'(t) = 2(1-t) (P1-P0) + 2t (P2-P1)
+            let analytic = (bezier.ctrl - bezier.start) * (2.0 * (1.0-t))
+                            + (bezier.end - bezier.ctrl) * (2.0 * t);
+            let err = derivative.eval(t) - analytic;
+            for axis in err {
+                assert!(axis.abs() < max_err);
+            }
+            assert!((bezier.dx(t) - analytic.axis(0)).abs() < max_err);
+            assert!((bezier.dy(t) - analytic.axis(1)).abs() < max_err);
+        }
+    }
 }
@@ -1,153 +1,234 @@
 use super::*;
 use super::point::Point;
-use super::line::LineSegment; 
-use super::quadratic_bezier::QuadraticBezier; 
+use super::bezier::Bezier;
 use super::cubic_bezier::CubicBezier;
 
-use num_traits::{float::Float};
-
-/// A Wrapper Class for Bezier curves that makes them rational, allowing 
-/// representation of conics by the use of weights for the control points
+/// Rational Bezier curve of arbitrary degree, which allows the exact representation of conics
+/// (e.g. circular arcs) by assigning a weight to each control point.
 /// A Rational Bezier is defined by:
 ///            SUM 0..n ( b(t, i, n) * p[i] * w[i] )
 /// B(t,n) = ----------------------------------------
 ///              SUM 0..n ( b(t, i, n) * w[i] )
-/// where b(t,i,n) is the bernstein polynomial of degree n, w[i] is the weights to apply for each control point p[i]
-pub enum RationalBezier<P: Point>
+/// where b(t,i,n) is the bernstein polynomial of degree n, w[i] is the weight to apply for each control point p[i].
+/// If all weights are equal, the curve is the ordinary (polynomial) Bezier curve of its control points.
+/// Generic parameters:
+/// P: Generic points 'P' as defined by there Point trait
+/// const generic parameters:
+/// N: Number of control points
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "P: Serialize",
+                                          deserialize = "P: Deserialize<'de>")))]
+pub struct RationalBezier<P, const N: usize>
+where
+P: Point + Copy,
 {
-    Linear(     LineSegment<P>,     [NativeFloat; 2]),
-    Quadratic(  QuadraticBezier<P>, [NativeFloat; 3]),
-    Cubic(      CubicBezier<P>,     [NativeFloat; 4]),
+    /// Control points which define the curve and hence its degree
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_array"))]
+    pub(crate) control_points: [P; N],
+    /// Weight of each control point
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_array"))]
+    pub(crate) weights: [NativeFloat; N],
 }
 
-
-impl<P> RationalBezier<P>
-where 
-P:  Sub<P, Output = P>
+impl<P, const N: usize> RationalBezier<P, {N}>
+where
+P: Add + Sub + Copy
     + Add<P, Output = P>
+    + Sub<P, Output = P>
     + Mul<NativeFloat, Output = P>
     + Point<Scalar = NativeFloat>,
-NativeFloat: Sub<NativeFloat, Output = NativeFloat> 
-+ Mul<NativeFloat, Output = NativeFloat> {
-
-    /// Creates a quadratic rational Bezier curve out of an existing linear bezier curve (just a line)
-    pub fn from_linear(curve: LineSegment<P>, weights: &[NativeFloat; 2]) -> RationalBezier<P>
-    {
-        return RationalBezier::Linear(curve, *weights)
+{
+    /// Create a new rational Bezier curve from the `control_points` and their `weights`.
+    /// The degree is defined as degree = control_points.len() - 1.
+    /// The weights are expected to be positive, so that the denominator of the curve never vanishes in [0,1].
+    pub fn new(control_points: [P; N], weights: [NativeFloat; N]) -> RationalBezier<P, {N}> {
+        RationalBezier{
+            control_points,
+            weights
+        }
     }
 
-    /// Creates a quadratic rational Bezier curve out of an existing quadratic bezier curve
-    pub fn from_quadratic(curve: QuadraticBezier<P>, weights: &[NativeFloat; 3]) -> RationalBezier<P>
-    {
-        return RationalBezier::Quadratic(curve, *weights)
+    pub fn control_points(&self) -> [P; N] {
+        self.control_points
     }
 
-    /// Creates a cubic rational Bezier curve out of an existing cubic bezier curve
-    pub fn from_cubic(curve: CubicBezier<P>, weights: &[NativeFloat; 4]) -> RationalBezier<P>
-    {
-        return RationalBezier::Cubic(curve, *weights)
+    pub fn weights(&self) -> [NativeFloat; N] {
+        self.weights
     }
 
-
-    /// Evaluates the Bernstein Polynomial b(t,i,n) of the necessary rank (quad. or cubic) adjusted with weights
-    /// b_w = sum_i=0_to_n( b(t,i,n) * w_i )
-    /// and returns its inverse 1/b_w used as the normalization factor for the rational curve
-    fn normalization_factor<F>(&self, t: F) -> F 
+    /// Evaluates the curve at t with the projective De Casteljau algorithm:
+    /// the control points are lifted to homogeneous coordinates (p[i] * w[i], w[i]),
+    /// which are interpolated like the control points of an ordinary Bezier curve.
+    /// The result is projected back by dividing by the interpolated weight (perspective divide).
+    /// Returns None if the interpolated weight vanishes at t, which can only happen if not all weights are positive.
+    pub fn eval<F>(&self, t: F) -> Option<P>
     where
     F: Float,
-    NativeFloat: Float + Into<F>
+    P: Mul<F, Output = P>,
+    NativeFloat: Sub<F, Output = F>
+        + Mul<F, Output = F>
+        + Into<F>
     {
-        match self {
-            Self::Linear(_, weights) => (1.0.into() - t) * weights[0].into() + t * weights[1].into(),
-
-            Self::Quadratic(_, weights) => (1.0.into() - t) * (1.0.into() - t) * weights[0].into()
-                                                + 2.0.into() * t * (1.0.into() - t) * weights[1].into()
-                                                + t * t * weights[2].into(),
-
-            Self::Cubic(_, weights) => (1.0.into() - t)*(1.0.into() - t) * (1.0.into() - t) * weights[0].into() 
-                                            + 3.0.into() * (1.0.into() - t) * (1.0.into() - t) * t * weights[1].into() 
-                                            + 3.0.into() * t * t * (1.0.into() - t) * weights[2].into()
-                                            + t * t * t * weights[3].into(),
+        // homogeneous control points and weights
+        let mut p: [P; N] = self.control_points;
+        let mut w: [F; N] = [F::zero(); N];
+        let mut max_weight = F::zero();
+        for i in 0..N {
+            w[i] = self.weights[i].into();
+            p[i] = p[i] * w[i];
+            max_weight = max_weight.max(w[i].abs());
         }
-    }
-
-
-        
-    pub fn eval<F>(&self, t: F) -> P 
-    where
-    F: Float
-        + Into<NativeFloat>
-        + From<NativeFloat>,
-    // NativeFloat: Sub<F, Output = F> 
-    //     + Add<F, Output = F>
-    //     + Mul<F, Output = F> 
-    //     + Into<F>
-    {
-        match self {
-            Self::Linear(segment, _) => segment.eval(t.into()) * self.normalization_factor(t).into(),
-            Self::Quadratic(segment, _) => segment.eval(t.into()) * self.normalization_factor(t).into(),
-            Self::Cubic(segment, _) => segment.eval(t.into()) * self.normalization_factor(t).into(),
+        // loop up to degree = control_points.len() -1
+        for i in 1..=N {
+            for j in 0..N - i {
+                p[j] = p[j] * (1.0 - t) + p[j+1] * t;
+                w[j] = w[j] * (1.0 - t) + w[j+1] * t;
+            }
         }
+        // the interpolated weight is a convex combination of the weights,
+        // so its rounding error is bounded relative to the largest weight
+        if w[0].abs() <= max_weight * EPSILON.into() {
+            return None
+        }
+        Some(p[0] * (F::one() / w[0]))
     }
+}
 
-    /// Evalues the Bezier segment using the numerically stable De Casteljau algorithm
-    /// For the linear case (degree = 1), it defaults to direct evaluation using simple interpolation
-    pub fn eval_casteljau<F>(&self, t: F) -> P 
-    where
-    F: Float
-        + Into<NativeFloat>
-        + From<NativeFloat>,
-    {
-        match self {
-            // for the linear case, de casteljau's algorithm equals direct evaluation (simple interpolation)
-            Self::Linear(segment, _) => segment.eval(t.into()) * self.normalization_factor(t).into(),
-            Self::Quadratic(segment, _) => segment.eval_casteljau(t.into()) * self.normalization_factor(t).into(),
-            Self::Cubic(segment, _) => segment.eval_casteljau(t.into()) * self.normalization_factor(t).into(),
+impl<P> RationalBezier<P, 4>
+where
+P: Add + Sub + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>
+    + Point<Scalar = NativeFloat>,
+{
+    /// Approximates the rational cubic with an ordinary cubic bezier curve for consumers which can't handle weights.
+    /// The approximation interpolates the end points and the point at t = 0.5 of the rational curve.
+    /// The inner control points start out at the end point tangents of the rational curve
+    /// (scaled by the ratio of the weights, e.g. ctrl1 = p0 + w1/w0 * (p1 - p0)), then both are moved
+    /// by the same offset to pass through the midpoint.
+    /// If all weights are equal, the result is the polynomial curve of the control points.
+    /// Returns None if the weights of the end points or the interpolated weight at t = 0.5 vanish.
+    pub fn to_nonrational_approx(&self) -> Option<CubicBezier<P>> {
+        let [p0, p1, p2, p3] = self.control_points;
+        let [w0, w1, w2, w3] = self.weights;
+        let max_weight = self.weights.iter().fold(0.0, |acc: NativeFloat, w| acc.max(w.abs()));
+        if w0.abs() <= max_weight * EPSILON || w3.abs() <= max_weight * EPSILON {
+            return None
         }
+        let mid = self.eval(0.5)?;
+        let ctrl1 = p0 + (p1 - p0) * (w1 / w0);
+        let ctrl2 = p3 + (p2 - p3) * (w2 / w3);
+        // the cubic at t = 0.5 is (p0 + 3 * ctrl1 + 3 * ctrl2 + p3) / 8
+        let offset = (mid * 8.0 - p0 - p3 - (ctrl1 + ctrl2) * 3.0) * (1.0 / 6.0);
+        Some(CubicBezier::new(p0, ctrl1 + offset, ctrl2 + offset, p3))
     }
+}
 
-
+/// An ordinary Bezier curve is a rational Bezier curve with unit weights
+impl<P, const N: usize> From<Bezier<P, N>> for RationalBezier<P, N>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    fn from(bezier: Bezier<P, N>) -> Self {
+        RationalBezier::new(bezier.control_points, [1.0; N])
+    }
 }
 
 
-#[cfg(test)]
-mod tests 
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
 {
     use super::*;
     use super::point_generic::PointN;
-    //use crate::num_traits::{Pow};
-    #[test]
-    /// Calls all available from_ constructors to catch some type/trait bound errors while coding
-    fn from_constructors() {
+    use super::quadratic_bezier::QuadraticBezier;
 
-        let line = LineSegment::new(
-            PointN::new([0f64,  1.77f64]),
-            PointN::new([3.2f64, -4f64]),
-        );
-        let rational_lin = RationalBezier::from_linear(line, &[1.1, 2.2]);
-
-        let quad = QuadraticBezier::new( 
-            PointN::new([0f64,  1.77f64]),
-            PointN::new([4.3f64, 3f64]),
-            PointN::new([3.2f64, -4f64]),
-        );
-        let rational_quad = RationalBezier::from_quadratic(quad, &[1.1, 7.5, 2.2]);
+    #[test]
+    fn unit_weights_match_bezier() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64]),
+                PointN::new([7.3f64, 2.7f64])];
+        let bezier: Bezier<PointN<f64, 2>, 5> = Bezier::new(points);
+        let rational = RationalBezier::from(bezier);
+        // equal weights other than one cancel out as well
+        let scaled = RationalBezier::new(points, [2.5; 5]);
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = bezier.eval(t);
+            assert!(rational.eval(t).unwrap().distance(&p) < max_err);
+            assert!(scaled.eval(t).unwrap().distance(&p) < max_err);
+        }
+        assert!(rational.eval(0.0).unwrap().distance(&points[0]) < max_err);
+        assert!(rational.eval(1.0).unwrap().distance(&points[4]) < max_err);
+    }
 
-        let cubic = CubicBezier::new( 
-            PointN::new([0f64,  1.77f64]),
-            PointN::new([1.1f64, -1f64]),
-            PointN::new([4.3f64, 3f64]),
-            PointN::new([3.2f64, -4f64]),
-        );
-        let rational_cubic = RationalBezier::from_cubic(cubic, &[1.1, 9.7, 7.5, 2.2]);
+    #[test]
+    fn quarter_circle() {
+        // the quarter of the unit circle from (1,0) to (0,1) is exactly represented by a rational quadratic
+        // with the weight cos(45°) = √2/2 for the inner control point
+        let points = [
+                PointN::new([1f64, 0f64]),
+                PointN::new([1f64, 1f64]),
+                PointN::new([0f64, 1f64])];
+        let rational = RationalBezier::new(points, [1.0, core::f64::consts::FRAC_1_SQRT_2, 1.0]);
+        let polynomial = QuadraticBezier::new(points[0], points[1], points[2]);
+
+        let origin = PointN::new([0f64, 0f64]);
+        let max_err = 1e-15;
+        let mut max_polynomial_err = 0f64;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
+            let t = t as f64 * 1f64/(nsteps as f64);
+            let p = rational.eval(t).unwrap();
+            assert!((p.distance(&origin) - 1.0).abs() < max_err);
+            max_polynomial_err = max_polynomial_err.max((polynomial.eval(t).distance(&origin) - 1.0).abs());
+        }
+        // the polynomial curve with the same control points bulges out of the circle
+        assert!(max_polynomial_err > 0.05);
+        // the rational curve is symmetric
+        let mid = rational.eval(0.5).unwrap();
+        assert!((mid.axis(0) - mid.axis(1)).abs() < max_err);
+    }
 
-        // just to make sure we can actually call the eval method as intended
-        let nsteps: usize =  10;                                
-        for t in 0..nsteps {
+    #[test]
+    fn rational_cubic() {
+        let points = [
+                PointN::new([0f64,  1.77f64]),
+                PointN::new([1.1f64, -1f64]),
+                PointN::new([4.3f64,3f64]),
+                PointN::new([3.2f64, -4f64])];
+        let cubic = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        let rational = RationalBezier::new(points, [0.7; 4]);
+        let approx = rational.to_nonrational_approx().unwrap();
+        let max_err = 1e-14;
+        let nsteps: usize = 100;
+        for t in 0..=nsteps {
             let t = t as f64 * 1f64/(nsteps as f64);
-            let _ = rational_lin.eval(t);
-            let _ = rational_quad.eval(t);
-            let _ = rational_cubic.eval(t);
+            assert!(rational.eval(t).unwrap().distance(&cubic.eval(t)) < max_err);
+            assert!(approx.eval(t).distance(&cubic.eval(t)) < max_err);
         }
+
+        // the approximation of a weighted curve matches it at the end points and the midpoint
+        let weighted = RationalBezier::new(points, [1.0, 2.0, 0.5, 1.5]);
+        let approx = weighted.to_nonrational_approx().unwrap();
+        assert!(approx.start == points[0] && approx.end == points[3]);
+        assert!(approx.eval(0.5).distance(&weighted.eval(0.5).unwrap()) < max_err);
+
+        // the denominator 1/8 * (w0 + 3*w1 + 3*w2 + w3) vanishes at t = 0.5
+        let singular = RationalBezier::new(points, [1.0, -1.0/3.0, -1.0/3.0, 1.0]);
+        assert!(singular.eval(0.5).is_none());
+        assert!(singular.eval(0.25).is_some());
+        assert!(singular.to_nonrational_approx().is_none());
+        assert!(RationalBezier::new(points, [0.0, 1.0, 1.0, 1.0]).to_nonrational_approx().is_none());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,293 @@
+//! Solvers for the real roots of polynomials with NativeFloat coefficients.
+//! The quadratic and cubic solvers are closed form, polynomials of any degree
+//! are solved iteratively with the Durand-Kerner method.
+//! All solvers return the distinct real roots in ascending order.
+
+use super::*;
+
+/// Relative tolerance (in multiples of EPSILON) for the rounding errors of the discriminants,
+/// within which they are considered to be zero by quadratic_roots() and cubic_roots()
+const DISCRIMINANT_TOLERANCE: NativeFloat = 64.0;
+/// Maximum number of Durand-Kerner iterations, see poly_roots()
+const POLY_MAX_ITERATIONS: usize = 500;
+/// Newton iterations to polish each real root found by poly_roots()
+const POLY_NEWTON_STEPS: usize = 2;
+
+
+/// Returns the real roots of a*t^2 + b*t + c.
+/// Falls back to the linear equation if a is (close to) zero and returns no roots
+/// if the polynomial is constant. A double root is returned once.
+pub fn quadratic_roots(a: NativeFloat, b: NativeFloat, c: NativeFloat) -> ArrayVec<[NativeFloat; 2]> {
+    let mut result = ArrayVec::new();
+    // check if can be handled below quadratic order
+    if a.abs() < EPSILON {
+        if b.abs() < EPSILON {
+            // no solutions
+            return result;
+        }
+        // is linear equation
+        result.push(-c / b);
+        return result;
+    }
+    // is quadratic equation
+    let delta = b * b - 4.0 * a * c;
+    // the discriminant is zero up to the rounding error of its terms: double root
+    if delta.abs() <= DISCRIMINANT_TOLERANCE * EPSILON * (b * b).max((4.0 * a * c).abs()) {
+        result.push(-b / (2.0 * a));
+    } else if delta > 0.0 {
+        // avoid the cancellation of -b + sqrt(delta) by computing the smaller root from the product of the roots
+        let q = -0.5 * (b + b.signum() * delta.sqrt());
+        let (r1, r2) = (q / a, c / q);
+        result.push(r1.min(r2));
+        result.push(r1.max(r2));
+        dedup_sorted(&mut result);
+    }
+    return result;
+}
+
+
+/// Returns the real roots of a*t^3 + b*t^2 + c*t + d using cardano's algorithm
+/// (code adapted from github.com/nical/lyon).
+/// Falls back to quadratic_roots() if a is (close to) zero. Repeated roots are returned once.
+pub fn cubic_roots(a: NativeFloat, b: NativeFloat, c: NativeFloat, d: NativeFloat) -> ArrayVec<[NativeFloat; 3]> {
+    let mut result = ArrayVec::new();
+
+    // check if can be handled below cubic order
+    if a.abs() < EPSILON {
+        result.extend(quadratic_roots(b, c, d).into_iter());
+        return result;
+    }
+
+    // is cubic equation -> use cardano's algorithm
+    let pi = core::f64::consts::PI as NativeFloat;
+    let frac_1_3 = 1.0 / 3.0;
+
+    let bn = b / a;
+    let cn = c / a;
+    let dn = d / a;
+
+    let delta0 = (3.0 * cn - bn * bn) / 9.0;
+    let delta1 = (9.0 * bn * cn - 27.0 * dn - 2.0 * bn * bn * bn) / 54.0;
+    let delta_01 = delta0 * delta0 * delta0 + delta1 * delta1;
+
+    // bounds of the rounding errors of delta0, delta1 and (to first order) delta_01
+    let tolerance = DISCRIMINANT_TOLERANCE * EPSILON;
+    let error0 = tolerance * (3.0 * cn).abs().max(bn * bn) / 9.0;
+    let error1 = tolerance * (9.0 * bn * cn).abs().max((27.0 * dn).abs()).max((2.0 * bn * bn * bn).abs()) / 54.0;
+    let error_01 = 3.0 * delta0 * delta0 * error0 + 2.0 * delta1.abs() * error1;
+
+    // the discriminant is zero up to rounding errors: all roots are real and at least two of them
+    // are equal, which is easily missed by either of the general branches below
+    if delta0.abs() <= error0 && delta1.abs() <= error1 {
+        // triple root
+        result.push(-bn * frac_1_3);
+    } else if delta_01.abs() <= error_01 {
+        // simple and double root
+        let s = delta1.signum() * delta1.abs().powf(frac_1_3);
+        result.push(-bn * frac_1_3 + 2.0 * s);
+        result.push(-bn * frac_1_3 - s);
+    } else if delta_01 > 0.0 {
+        // one real root
+        let delta_p_sqrt = delta1 + delta_01.sqrt();
+        let delta_m_sqrt = delta1 - delta_01.sqrt();
+
+        let s = delta_p_sqrt.signum() * delta_p_sqrt.abs().powf(frac_1_3);
+        let t = delta_m_sqrt.signum() * delta_m_sqrt.abs().powf(frac_1_3);
+
+        result.push(-bn * frac_1_3 + (s + t));
+    } else {
+        // three distinct real roots
+        let theta = (delta1 / (-delta0 * delta0 * delta0).sqrt()).acos();
+        let two_sqrt_delta0 = 2.0 * (-delta0).sqrt();
+        result.push(two_sqrt_delta0 * (theta * frac_1_3).cos() - bn * frac_1_3);
+        result.push(two_sqrt_delta0 * ((theta + 2.0 * pi) * frac_1_3).cos() - bn * frac_1_3);
+        result.push(two_sqrt_delta0 * ((theta + 4.0 * pi) * frac_1_3).cos() - bn * frac_1_3);
+    }
+    result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    dedup_sorted(&mut result);
+    result
+}
+
+
+/// Returns the real roots of the polynomial of degree N-1 with the coefficients
+/// in order of decreasing degree, i.e. coeffs[0]*t^(N-1) + ... + coeffs[N-1].
+/// Leading coefficients which are (close to) zero are ignored. All complex roots are approximated
+/// simultaneously with the Durand-Kerner method, those with a negligible imaginary part are
+/// polished with Newton iterations and returned as real roots.
+/// Repeated roots only converge to about half the precision of simple roots,
+/// they are returned once.
+pub fn poly_roots<const N: usize>(coeffs: [NativeFloat; N]) -> ArrayVec<[NativeFloat; N]> {
+    let mut result = ArrayVec::new();
+    let first = match coeffs.iter().position(|c| c.abs() >= EPSILON) {
+        Some(first) => first,
+        None => return result,
+    };
+    // monic polynomial of degree n, the leading coefficient of 1 is implicit
+    let n = N - 1 - first;
+    let mut monic = [0.0; N];
+    for i in 0..n {
+        monic[i] = coeffs[first + 1 + i] / coeffs[first];
+    }
+    let eval = |z: (NativeFloat, NativeFloat)| {
+        let mut value = (1.0, 0.0);
+        for c in monic[..n].iter() {
+            value = complex_mul(value, z);
+            value.0 += c;
+        }
+        value
+    };
+
+    // initial guesses on a spiral that is neither real nor a root of unity
+    let mut z = [(0.0, 0.0); N];
+    let mut power = (1.0, 0.0);
+    for i in 0..n {
+        z[i] = power;
+        power = complex_mul(power, (0.4, 0.9));
+    }
+    for _ in 0..POLY_MAX_ITERATIONS {
+        let mut max_step: NativeFloat = 0.0;
+        for i in 0..n {
+            let mut denominator = (1.0, 0.0);
+            for j in 0..n {
+                if i != j {
+                    denominator = complex_mul(denominator, (z[i].0 - z[j].0, z[i].1 - z[j].1));
+                }
+            }
+            let step = complex_div(eval(z[i]), denominator);
+            z[i] = (z[i].0 - step.0, z[i].1 - step.1);
+            max_step = max_step.max(step.0.abs() + step.1.abs());
+        }
+        if max_step < EPSILON {
+            break;
+        }
+    }
+
+    let tolerance = EPSILON.sqrt();
+    for i in 0..n {
+        let mut t = z[i].0;
+        if z[i].1.abs() > tolerance * (1.0 + t.abs()) {
+            continue;
+        }
+        for _ in 0..POLY_NEWTON_STEPS {
+            let (mut value, mut slope) = (1.0, 0.0);
+            for c in monic[..n].iter() {
+                slope = slope * t + value;
+                value = value * t + c;
+            }
+            if slope.abs() < EPSILON {
+                break;
+            }
+            t -= value / slope;
+        }
+        if result.iter().all(|r: &NativeFloat| (r - t).abs() > tolerance * (1.0 + t.abs())) {
+            result.push(t);
+        }
+    }
+    result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    result
+}
+
+/// Removes roots which are closer than EPSILON to their predecessor from the sorted roots
+fn dedup_sorted<A>(roots: &mut ArrayVec<A>)
+where
+A: tinyvec::Array<Item = NativeFloat>,
+{
+    let mut i = 1;
+    while i < roots.len() {
+        if roots[i] - roots[i - 1] < EPSILON {
+            roots.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn complex_mul(a: (NativeFloat, NativeFloat), b: (NativeFloat, NativeFloat)) -> (NativeFloat, NativeFloat) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn complex_div(a: (NativeFloat, NativeFloat), b: (NativeFloat, NativeFloat)) -> (NativeFloat, NativeFloat) {
+    let norm = b.0 * b.0 + b.1 * b.1;
+    ((a.0 * b.0 + a.1 * b.1) / norm, (a.1 * b.0 - a.0 * b.1) / norm)
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+
+    fn assert_roots(roots: &[f64], expected: &[f64], max_err: f64) {
+        assert!(roots.len() == expected.len());
+        for (r, e) in roots.iter().zip(expected.iter()) {
+            assert!((r - e).abs() < max_err);
+        }
+    }
+
+    #[test]
+    fn quadratic() {
+        // (t - 1)(t - 3)
+        assert_roots(&quadratic_roots(2.0, -8.0, 6.0), &[1.0, 3.0], 1e-14);
+        // small root without cancellation
+        let roots = quadratic_roots(1.0, -1e8, 1.0);
+        assert!((roots[0] - 1e-8).abs() < 1e-22 && (roots[1] - 1e8).abs() < 1e-6);
+        // repeated root
+        assert_roots(&quadratic_roots(1.0, -4.0, 4.0), &[2.0], 1e-14);
+        // complex roots
+        assert_roots(&quadratic_roots(1.0, 0.0, 1.0), &[], 0.0);
+        // linear and constant
+        assert_roots(&quadratic_roots(0.0, 2.0, -1.0), &[0.5], 1e-14);
+        assert_roots(&quadratic_roots(0.0, 0.0, 1.0), &[], 0.0);
+    }
+
+    #[test]
+    fn cubic() {
+        // (t + 2)(t - 0.5)(t - 3)
+        assert_roots(&cubic_roots(1.0, -1.5, -5.5, 3.0), &[-2.0, 0.5, 3.0], 1e-12);
+        // double root at 1 and a simple root at -2: (t - 1)^2 (t + 2)
+        assert_roots(&cubic_roots(1.0, 0.0, -3.0, 2.0), &[-2.0, 1.0], 1e-7);
+        // triple root
+        assert_roots(&cubic_roots(1.0, -3.0, 3.0, -1.0), &[1.0], 1e-7);
+        // one real and two complex roots: (t - 2)(t^2 + 1)
+        assert_roots(&cubic_roots(1.0, -2.0, 1.0, -2.0), &[2.0], 1e-12);
+        // lower degrees
+        assert_roots(&cubic_roots(0.0, 2.0, -8.0, 6.0), &[1.0, 3.0], 1e-14);
+    }
+
+    #[test]
+    fn repeated_roots_with_rounding() {
+        // coefficients of the expanded products are not exact, so the discriminants
+        // are only zero up to rounding errors
+        let (r1, r2) = (1.0 / 3.0, 0.7);
+        // (t - r1)^2
+        assert_roots(&quadratic_roots(1.0, -2.0 * r1, r1 * r1), &[r1], 1e-7);
+        // (t - r1)^2 (t - r2)
+        let (b, c, d) = (-(2.0 * r1 + r2), r1 * r1 + 2.0 * r1 * r2, -r1 * r1 * r2);
+        assert_roots(&cubic_roots(1.0, b, c, d), &[r1, r2], 1e-7);
+        assert_roots(&cubic_roots(-3.0, -3.0 * b, -3.0 * c, -3.0 * d), &[r1, r2], 1e-7);
+        // (t - r2)^3
+        let (b, c, d) = (-3.0 * r2, 3.0 * r2 * r2, -r2 * r2 * r2);
+        assert_roots(&cubic_roots(1.0, b, c, d), &[r2], 1e-5);
+        // close, but distinct roots are kept
+        assert_roots(&quadratic_roots(1.0, -(0.5 + 0.5001), 0.5 * 0.5001), &[0.5, 0.5001], 1e-12);
+    }
+
+    #[test]
+    fn poly() {
+        // (t + 1)(t - 0.25)(t - 2)(t - 5) expanded
+        assert_roots(&poly_roots([1.0, -6.25, 4.5, 9.25, -2.5]), &[-1.0, 0.25, 2.0, 5.0], 1e-12);
+        // leading zeros are ignored and the scale doesn't matter
+        assert_roots(&poly_roots([0.0, 0.0, 3.0, -4.5, -16.5, 9.0]), &[-2.0, 0.5, 3.0], 1e-12);
+        // complex roots: (t^2 + 1)(t^2 + 4) and (t - 2)(t^2 + 1)
+        assert_roots(&poly_roots([1.0, 0.0, 5.0, 0.0, 4.0]), &[], 0.0);
+        assert_roots(&poly_roots([1.0, -2.0, 1.0, -2.0]), &[2.0], 1e-12);
+        // double root: (t - 1)^2 (t + 2)
+        assert_roots(&poly_roots([1.0, 0.0, -3.0, 2.0]), &[-2.0, 1.0], 1e-7);
+        // constant and linear
+        assert_roots(&poly_roots([0.0, 0.0, 1.0]), &[], 0.0);
+        assert_roots(&poly_roots([2.0, -1.0]), &[0.5], 1e-14);
+        // agrees with the closed form solvers
+        let cubic = poly_roots([1.0, -1.5, -5.5, 3.0]);
+        assert_roots(&cubic, &cubic_roots(1.0, -1.5, -5.5, 3.0), 1e-12);
+    }
+}
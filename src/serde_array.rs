@@ -0,0 +1,54 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::ser::SerializeTuple;
+use serde::de::{Visitor, SeqAccess, Error};
+
+/// (De-)Serialization of const generic arrays [T; N] as fixed size tuples,
+/// because serde only implements its traits for arrays up to a length of 32.
+/// Use with the field attribute #[serde(with = "super::serde_array")]
+pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+S: Serializer,
+T: Serialize,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for element in array.iter() {
+        tuple.serialize_element(element)?;
+    }
+    tuple.end()
+}
+
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+D: Deserializer<'de>,
+T: Deserialize<'de> + Default + Copy,
+{
+    deserializer.deserialize_tuple(N, ArrayVisitor::<T, N>(PhantomData))
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+where
+T: Deserialize<'de> + Default + Copy,
+{
+    type Value = [T; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an array of length {}", N)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<[T; N], A::Error>
+    where
+    A: SeqAccess<'de>,
+    {
+        // initialize with default values, every element gets overwritten or we return an error
+        let mut array = [T::default(); N];
+        for i in 0..N {
+            array[i] = seq.next_element()?.ok_or_else(|| Error::invalid_length(i, &self))?;
+        }
+        Ok(array)
+    }
+}
@@ -0,0 +1,154 @@
+//! Conversion of curves from and to SVG path data (the 'd' attribute of a path element).
+//! Only 2D curves are supported, the first two axes of the points are used as x and y.
+
+use core::iter::once;
+
+use super::*;
+use super::point_generic::PointN;
+use super::cubic_bezier::CubicBezier;
+
+#[cfg(feature = "alloc")]
+use {
+    super::point::Point,
+    alloc::string::String,
+    core::fmt::Write,
+};
+
+
+#[cfg(feature = "alloc")]
+impl<P> CubicBezier<P>
+where
+P: Point<Scalar = NativeFloat>
+    + Copy
+    + Add<P, Output = P>
+    + Sub<P, Output = P>
+    + Mul<NativeFloat, Output = P>,
+{
+    /// Returns the curve as SVG path data of the form "M x0 y0 C x1 y1 x2 y2 x3 y3"
+    pub fn to_svg_path(&self) -> String {
+        to_svg_path(&[*self])
+    }
+}
+
+
+/// Returns the path data for a sequence of connected cubic curves,
+/// consisting of a single "M" command to the start of the first curve followed by
+/// one "C" command per curve. The start points of all but the first curve are ignored.
+/// An empty slice yields an empty String.
+#[cfg(feature = "alloc")]
+pub fn to_svg_path<P>(segments: &[CubicBezier<P>]) -> String
+where
+P: Point<Scalar = NativeFloat>,
+{
+    let mut path = String::new();
+    if let Some(first) = segments.first() {
+        // writing to a String can't fail
+        let _ = write!(path, "M {} {}", first.start.axis(0), first.start.axis(1));
+        for segment in segments {
+            let _ = write!(path, " C {} {} {} {} {} {}",
+                            segment.ctrl1.axis(0), segment.ctrl1.axis(1),
+                            segment.ctrl2.axis(0), segment.ctrl2.axis(1),
+                            segment.end.axis(0), segment.end.axis(1));
+        }
+    }
+    path
+}
+
+
+/// Parses a single cubic curve from path data of the form "M x0 y0 C x1 y1 x2 y2 x3 y3".
+/// Only absolute coordinates are supported, numbers may be separated by whitespace
+/// and/or commas and the command letters may be attached to the following number.
+/// Returns None if the input is malformed or contains anything beyond the curve.
+pub fn from_svg_cubic(d: &str) -> Option<CubicBezier<PointN<NativeFloat, 2>>> {
+    let mut tokens = d
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .flat_map(|s| {
+            // split a command letter off a number following it without a separator, e.g. "M0"
+            let (command, rest) = if s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                s.split_at(1)
+            } else {
+                ("", s)
+            };
+            once(command).chain(once(rest)).filter(|s| !s.is_empty())
+        });
+
+    if tokens.next()? != "M" {
+        return None;
+    }
+    let start = parse_point(&mut tokens)?;
+    if tokens.next()? != "C" {
+        return None;
+    }
+    let ctrl1 = parse_point(&mut tokens)?;
+    let ctrl2 = parse_point(&mut tokens)?;
+    let end = parse_point(&mut tokens)?;
+    if tokens.next().is_some() {
+        return None;
+    }
+    return Some(CubicBezier::new(start, ctrl1, ctrl2, end));
+}
+
+/// Parses the next two tokens as the x and y coordinate of a point
+fn parse_point<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Option<PointN<NativeFloat, 2>> {
+    let x = tokens.next()?.parse().ok()?;
+    let y = tokens.next()?.parse().ok()?;
+    return Some(PointN::new([x, y]));
+}
+
+
+#[cfg(all(test, not(feature = "f32")))]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn parse_path() {
+        let expected = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        for d in ["M 0 1.77 C 1.1 -1 4.3 3 3.2 -4",
+                  "M0,1.77 C1.1,-1 4.3,3 3.2,-4",
+                  "  M 0, 1.77\nC 1.1 , -1, 4.3 3,3.2 -4 "].iter() {
+            assert!(from_svg_cubic(d) == Some(expected));
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        // missing C command
+        assert!(from_svg_cubic("M 0 1.77 1.1 -1 4.3 3 3.2 -4").is_none());
+        // relative commands are not supported
+        assert!(from_svg_cubic("m 0 1.77 c 1.1 -1 4.3 3 3.2 -4").is_none());
+        // missing coordinate
+        assert!(from_svg_cubic("M 0 1.77 C 1.1 -1 4.3 3 3.2").is_none());
+        // not a number
+        assert!(from_svg_cubic("M 0 1.77 C 1.1 -1 4.3 x 3.2 -4").is_none());
+        // trailing data
+        assert!(from_svg_cubic("M 0 1.77 C 1.1 -1 4.3 3 3.2 -4 L 0 0").is_none());
+        assert!(from_svg_cubic("").is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn export_path() {
+        let bezier = CubicBezier::new(
+            PointN::new([0f64, 1.77f64]),
+            PointN::new([1.1f64, -1f64]),
+            PointN::new([4.3f64, 3f64]),
+            PointN::new([3.2f64, -4f64]),
+        );
+        assert!(bezier.to_svg_path() == "M 0 1.77 C 1.1 -1 4.3 3 3.2 -4");
+        assert!(from_svg_cubic(&bezier.to_svg_path()) == Some(bezier));
+
+        let (left, right) = bezier.split(0.5);
+        let path = to_svg_path(&[left, right]);
+        assert!(path.starts_with("M 0 1.77 C "));
+        assert!(path.ends_with(" 3.2 -4"));
+        assert!(path.matches('C').count() == 2);
+        assert!(to_svg_path::<PointN<f64, 2>>(&[]).is_empty());
+    }
+}